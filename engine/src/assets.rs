@@ -0,0 +1,184 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+pub struct Handle<T> {
+    index: u32,
+    generation: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    pub fn index(self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+struct Slot<T> {
+    value: Option<T>,
+    generation: u32,
+    revision: u64,
+}
+
+pub struct Assets<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+    next_revision: u64,
+}
+
+impl<T> Default for Assets<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            next_revision: 1,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Assets<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Assets").field("len", &self.len).finish()
+    }
+}
+
+impl<T> Assets<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, value: T) -> Handle<T> {
+        let revision = self.bump_revision();
+        self.len += 1;
+        let index = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = Some(value);
+                slot.revision = revision;
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    value: Some(value),
+                    generation: 0,
+                    revision,
+                });
+                self.slots.len() as u32 - 1
+            }
+        };
+        Handle {
+            index,
+            generation: self.slots[index as usize].generation,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn remove(&mut self, handle: Handle<T>) -> Option<T> {
+        let slot = self.slot_mut(handle)?;
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        self.slots
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)?
+            .value
+            .as_ref()
+    }
+
+    // Mutable access marks the asset dirty so backends re-upload it.
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        self.get(handle)?;
+        let revision = self.bump_revision();
+        let slot = self.slot_mut(handle)?;
+        slot.revision = revision;
+        slot.value.as_mut()
+    }
+
+    pub fn revision(&self, handle: Handle<T>) -> Option<u64> {
+        self.get(handle)?;
+        Some(self.slots[handle.index as usize].revision)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let value = slot.value.as_ref()?;
+            Some((
+                Handle {
+                    index: index as u32,
+                    generation: slot.generation,
+                    marker: PhantomData,
+                },
+                value,
+            ))
+        })
+    }
+
+    pub(crate) fn iter_revisions(&self) -> impl Iterator<Item = (Handle<T>, u64, &T)> + '_ {
+        self.iter()
+            .map(|(handle, value)| (handle, self.slots[handle.index as usize].revision, value))
+    }
+
+    fn slot_mut(&mut self, handle: Handle<T>) -> Option<&mut Slot<T>> {
+        self.slots
+            .get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+    }
+
+    fn bump_revision(&mut self) -> u64 {
+        let revision = self.next_revision;
+        self.next_revision += 1;
+        revision
+    }
+}
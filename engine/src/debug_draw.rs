@@ -0,0 +1,83 @@
+use crate::physics::PhysicsDebugOptions;
+use glam::{Vec3, Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLine {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub color: Vec4,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DebugDraw {
+    pub enabled: bool,
+    pub physics: PhysicsDebugOptions,
+    lines: Vec<DebugLine>,
+}
+
+impl DebugDraw {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    pub fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Vec4) {
+        self.lines.push(DebugLine { start, end, color });
+    }
+
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, color: Vec4) {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        };
+        for i in 0..8 {
+            for axis in [1, 2, 4] {
+                if i & axis == 0 {
+                    self.line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    pub fn cross(&mut self, center: Vec3, size: f32, color: Vec4) {
+        let half = size * 0.5;
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            self.line(center - axis * half, center + axis * half, color);
+        }
+    }
+
+    pub fn lines(&self) -> &[DebugLine] {
+        &self.lines
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    pub(crate) fn take_lines(&mut self) -> Vec<DebugLine> {
+        std::mem::take(&mut self.lines)
+    }
+
+    pub(crate) fn restore_lines(&mut self, mut lines: Vec<DebugLine>) {
+        lines.clear();
+        self.lines = lines;
+    }
+}
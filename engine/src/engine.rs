@@ -1,12 +1,27 @@
+use crate::debug_draw::DebugDraw;
 use crate::error::EngineError;
-use crate::physics::PhysicsWorld;
-use crate::renderer::{RenderFrame, Renderer};
+use crate::game::{EngineContext, Game};
+use crate::physics::{
+    pull_body_poses, push_kinematic_targets, PhysicsConfig, PhysicsEvent, PhysicsWorld,
+};
+use crate::renderer::{
+    collect_draw_items, AnimationConfig, AnimationEncoder, AnimationFormat, BackendKind, LoopMode,
+    RenderFrame, RenderTarget, Renderer, SpriteBatcher,
+};
 use crate::scene::Scene;
-use std::time::Instant;
-use tracing::info;
+use crate::text::TextRenderer;
+use crate::time::FixedTimestep;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info};
 use winit::dpi::LogicalSize;
-use winit::event::{Event, WindowEvent};
+use winit::event::{ElementState, Event, KeyEvent, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::WindowBuilder;
 
 pub type EngineResult<T> = Result<T, EngineError>;
@@ -17,6 +32,14 @@ pub struct EngineConfig {
     pub width: u32,
     pub height: u32,
     pub target_fps: u32,
+    pub fixed_update_hz: u32,
+    pub max_substeps: u32,
+    pub headless: Option<HeadlessConfig>,
+    pub render_backends: Vec<BackendKind>,
+    pub capture: Option<CaptureConfig>,
+    pub recording: Option<RecordingConfig>,
+    pub physics: PhysicsConfig,
+    pub debug_draw: bool,
 }
 
 impl Default for EngineConfig {
@@ -26,38 +49,194 @@ impl Default for EngineConfig {
             width: 1280,
             height: 720,
             target_fps: 60,
+            fixed_update_hz: 60,
+            max_substeps: 5,
+            headless: None,
+            render_backends: BackendKind::default_fallback_order(),
+            capture: None,
+            recording: None,
+            physics: PhysicsConfig::default(),
+            debug_draw: false,
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    pub every_n_frames: u32,
+    pub output_dir: PathBuf,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            every_n_frames: 60,
+            output_dir: PathBuf::from("captures"),
+        }
+    }
+}
+
+// Records rendered frames into an animated GIF or APNG (picked from the output
+// extension) that is written when the engine shuts down.
+#[derive(Debug, Clone)]
+pub struct RecordingConfig {
+    pub output: PathBuf,
+    // Frame delay is every_n_frames / target_fps. GIF viewers clamp delays
+    // under 20ms, so 60 fps runs are recorded at 30 by default.
+    pub every_n_frames: u32,
+    pub loop_mode: LoopMode,
+    pub palette_size: u16,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            output: PathBuf::from("recording.gif"),
+            every_n_frames: 2,
+            loop_mode: LoopMode::Forever,
+            palette_size: 256,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessConfig {
+    pub max_frames: Option<u64>,
+    pub realtime: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum EngineEvent {
     Startup,
+    FixedUpdate { delta_seconds: f32 },
     Frame { delta_seconds: f32 },
     Shutdown,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct StopHandle(Arc<AtomicBool>);
+
+impl StopHandle {
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
 pub struct Engine {
     config: EngineConfig,
     renderer: Option<Renderer>,
     physics: PhysicsWorld,
+    frame_events: Vec<PhysicsEvent>,
     scene: Scene,
+    debug_draw: DebugDraw,
+    text: TextRenderer,
+    recorder: Option<AnimationEncoder<BufWriter<File>>>,
+    viewport: (u32, u32),
+    stop: StopHandle,
+    frame_index: u64,
+    timestep: FixedTimestep,
 }
 
 impl Engine {
     pub fn new(config: EngineConfig) -> EngineResult<Self> {
         tracing_subscriber::fmt::try_init().ok();
-        let physics = PhysicsWorld::new();
+        let physics = PhysicsWorld::with_config(config.physics);
         let scene = Scene::default();
+        let timestep = FixedTimestep::new(config.fixed_update_hz, config.max_substeps);
+        let debug_draw = DebugDraw::new(config.debug_draw);
+        let viewport = (config.width, config.height);
+        let recorder = config
+            .recording
+            .as_ref()
+            .map(|recording| recording_encoder(recording, config.target_fps))
+            .transpose()?;
         Ok(Self {
             config,
             renderer: None,
             physics,
+            frame_events: Vec::new(),
             scene,
+            debug_draw,
+            text: TextRenderer::new(),
+            recorder,
+            viewport,
+            stop: StopHandle::default(),
+            frame_index: 0,
+            timestep,
         })
     }
 
-    pub fn run(mut self) -> EngineResult<()> {
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop.clone()
+    }
+
+    pub fn run(self) -> EngineResult<()> {
+        self.run_with(())
+    }
+
+    pub fn run_with(self, game: impl Game + 'static) -> EngineResult<()> {
+        if let Some(headless) = self.config.headless.clone() {
+            return self.run_headless(headless, game);
+        }
+        self.run_windowed(game)
+    }
+
+    pub fn run_headless(
+        mut self,
+        headless: HeadlessConfig,
+        mut game: impl Game,
+    ) -> EngineResult<()> {
+        info!("engine startup (headless)");
+        self.prepare_capture()?;
+        if self.config.capture.is_some() || self.recorder.is_some() {
+            self.renderer = Some(Renderer::offscreen(
+                self.config.width,
+                self.config.height,
+                &self.config.render_backends,
+            )?);
+        }
+        let frame_time = 1.0 / self.config.target_fps.max(1) as f32;
+        let frame_duration = Duration::from_secs_f32(frame_time);
+        let mut next_frame = Instant::now();
+        self.emit(&mut game, &EngineEvent::Startup);
+
+        while !self.stop.is_stopped() {
+            if headless.max_frames.is_some_and(|max| self.frame_index >= max) {
+                break;
+            }
+            self.update(frame_time, &mut game);
+            let capture = self.capture_due();
+            let record = self.record_due();
+            if capture || record {
+                self.render(self.frame_index as f32 * frame_time);
+            }
+            if capture {
+                self.capture();
+            }
+            if record {
+                self.record();
+            }
+            self.frame_index += 1;
+            if headless.realtime {
+                next_frame += frame_duration;
+                let now = Instant::now();
+                if next_frame > now {
+                    std::thread::sleep(next_frame - now);
+                } else {
+                    next_frame = now;
+                }
+            }
+        }
+        self.emit(&mut game, &EngineEvent::Shutdown);
+        info!("engine shutdown after {} frames", self.frame_index);
+        self.finish_recording()
+    }
+
+    fn run_windowed(mut self, mut game: impl Game + 'static) -> EngineResult<()> {
         let event_loop = EventLoop::new().map_err(|err| {
             EngineError::WindowCreation(format!("event loop init failed: {err:?}"))
         })?;
@@ -65,10 +244,18 @@ impl Engine {
             .with_title(self.config.title.clone())
             .with_inner_size(LogicalSize::new(self.config.width, self.config.height))
             .build(&event_loop)
+            .map(Arc::new)
             .map_err(|err| EngineError::WindowCreation(err.to_string()))?;
 
-        self.renderer = Some(Renderer::new(&window)?);
+        self.prepare_capture()?;
+        let size = window.inner_size();
+        self.viewport = (size.width, size.height);
+        self.renderer = Some(Renderer::new(
+            RenderTarget::Window(window.clone()),
+            &self.config.render_backends,
+        )?);
         info!("engine startup");
+        self.emit(&mut game, &EngineEvent::Startup);
         let mut last_frame = Instant::now();
         let start_time = Instant::now();
         let target_frame_time = 1.0 / self.config.target_fps.max(1) as f32;
@@ -83,10 +270,24 @@ impl Engine {
                             event_loop.exit();
                         }
                         WindowEvent::Resized(size) => {
+                            self.viewport = (size.width, size.height);
                             if let Some(renderer) = self.renderer.as_mut() {
                                 renderer.resize(size.width, size.height);
                             }
                         }
+                        WindowEvent::KeyboardInput {
+                            event:
+                                KeyEvent {
+                                    physical_key: PhysicalKey::Code(KeyCode::F3),
+                                    state: ElementState::Pressed,
+                                    repeat: false,
+                                    ..
+                                },
+                            ..
+                        } => {
+                            let enabled = self.debug_draw.toggle();
+                            info!("debug draw {}", if enabled { "on" } else { "off" });
+                        }
                         WindowEvent::RedrawRequested => {
                             let now = Instant::now();
                             let delta = now.duration_since(last_frame).as_secs_f32();
@@ -94,21 +295,30 @@ impl Engine {
                                 return;
                             }
                             last_frame = now;
-                            self.physics.step(delta);
-                            self.scene.update(delta);
-                            let frame = RenderFrame {
-                                clear_color: self.scene.environment.clear_color,
-                                time_seconds: start_time.elapsed().as_secs_f32(),
-                            };
-                            if let Some(renderer) = self.renderer.as_mut() {
-                                if let Err(err) = renderer.render(frame) {
-                                    tracing::error!("render error: {err}");
-                                }
+                            self.update(delta, &mut game);
+                            self.render(start_time.elapsed().as_secs_f32());
+                            if self.capture_due() {
+                                self.capture();
+                            }
+                            if self.record_due() {
+                                self.record();
                             }
+                            self.frame_index += 1;
                         }
                         _ => {}
                     },
+                    Event::LoopExiting => {
+                        self.emit(&mut game, &EngineEvent::Shutdown);
+                        if let Err(err) = self.finish_recording() {
+                            error!("recording failed: {err}");
+                        }
+                    }
                     Event::AboutToWait => {
+                        if self.stop.is_stopped() {
+                            info!("engine shutdown");
+                            event_loop.exit();
+                            return;
+                        }
                         window.request_redraw();
                     }
                     _ => {}
@@ -119,4 +329,164 @@ impl Engine {
             })?;
         Ok(())
     }
+
+    fn update(&mut self, delta_seconds: f32, game: &mut dyn Game) {
+        self.debug_draw.clear();
+        let steps = self.timestep.advance(delta_seconds);
+        let step_seconds = self.timestep.step_seconds();
+        self.frame_events.clear();
+        for _ in 0..steps {
+            self.emit(
+                game,
+                &EngineEvent::FixedUpdate {
+                    delta_seconds: step_seconds,
+                },
+            );
+            push_kinematic_targets(&mut self.physics, &self.scene.registry);
+            self.physics.step(step_seconds);
+            self.frame_events.extend(self.physics.events().copied());
+            pull_body_poses(&self.physics, &mut self.scene.registry);
+        }
+        self.scene.update(delta_seconds);
+        self.emit(game, &EngineEvent::Frame { delta_seconds });
+    }
+
+    fn emit(&mut self, game: &mut dyn Game, event: &EngineEvent) {
+        let mut ctx = EngineContext {
+            scene: &mut self.scene,
+            physics: &mut self.physics,
+            physics_events: &self.frame_events,
+            debug_draw: &mut self.debug_draw,
+            frame_index: self.frame_index,
+            stop: &self.stop,
+        };
+        game.on_event(event, &mut ctx);
+    }
+
+    fn render(&mut self, time_seconds: f32) {
+        let debug_lines = if self.debug_draw.enabled {
+            let options = self.debug_draw.physics;
+            self.physics.debug_draw(&options, &mut self.debug_draw);
+            self.debug_draw.take_lines()
+        } else {
+            Vec::new()
+        };
+        let (width, height) = self.viewport;
+        if width > 0 && height > 0 {
+            self.scene.main_camera.aspect_ratio = width as f32 / height as f32;
+        }
+        let view_projection = self.scene.main_camera.view_projection();
+        let mut sprites = SpriteBatcher::new();
+        sprites.extend_from_scene(&self.scene);
+        self.text.queue_scene(&mut self.scene, &mut sprites);
+        let frame = RenderFrame {
+            clear_color: self.scene.environment.clear_color,
+            time_seconds,
+            interpolation_alpha: self.timestep.alpha(),
+            view_projection,
+            draw_items: collect_draw_items(&self.scene),
+            sprites: sprites.build(view_projection),
+            debug_lines,
+        };
+        if let Some(renderer) = self.renderer.as_mut() {
+            if let Err(err) = renderer.sync_meshes(&self.scene.meshes) {
+                error!("mesh upload failed: {err}");
+            }
+            if let Err(err) = renderer.sync_textures(&self.scene.textures) {
+                error!("texture upload failed: {err}");
+            }
+            if let Err(err) = renderer.render(&frame) {
+                error!("render error: {err}");
+            }
+        }
+        self.debug_draw.restore_lines(frame.debug_lines);
+    }
+
+    fn prepare_capture(&self) -> EngineResult<()> {
+        if let Some(capture) = self.config.capture.as_ref() {
+            std::fs::create_dir_all(&capture.output_dir).map_err(|err| {
+                EngineError::Io(format!("{}: {err}", capture.output_dir.display()))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn capture_due(&self) -> bool {
+        self.config
+            .capture
+            .as_ref()
+            .is_some_and(|capture| {
+                self.frame_index
+                    .is_multiple_of(capture.every_n_frames.max(1) as u64)
+            })
+    }
+
+    fn capture(&mut self) {
+        let (Some(capture), Some(renderer)) = (self.config.capture.as_ref(), self.renderer.as_mut())
+        else {
+            return;
+        };
+        let path = capture
+            .output_dir
+            .join(format!("frame_{:06}.png", self.frame_index));
+        match renderer.capture_frame().and_then(|image| image.save_png(&path)) {
+            Ok(()) => info!("captured {}", path.display()),
+            Err(err) => error!("frame capture failed: {err}"),
+        }
+    }
+
+    fn record_due(&self) -> bool {
+        self.recorder.is_some()
+            && self.config.recording.as_ref().is_some_and(|recording| {
+                self.frame_index
+                    .is_multiple_of(recording.every_n_frames.max(1) as u64)
+            })
+    }
+
+    fn record(&mut self) {
+        let (Some(recorder), Some(renderer)) = (self.recorder.as_mut(), self.renderer.as_mut())
+        else {
+            return;
+        };
+        if let Err(err) = renderer
+            .capture_frame()
+            .and_then(|image| recorder.push_frame(image))
+        {
+            error!("recording frame failed: {err}");
+        }
+    }
+
+    fn finish_recording(&mut self) -> EngineResult<()> {
+        let (Some(recorder), Some(recording)) =
+            (self.recorder.take(), self.config.recording.as_ref())
+        else {
+            return Ok(());
+        };
+        let frames = recorder.len();
+        recorder.finish()?;
+        info!("recorded {frames} frames to {}", recording.output.display());
+        Ok(())
+    }
+}
+
+fn recording_encoder(
+    recording: &RecordingConfig,
+    target_fps: u32,
+) -> EngineResult<AnimationEncoder<BufWriter<File>>> {
+    let format = AnimationFormat::from_path(&recording.output).ok_or_else(|| {
+        EngineError::Image(format!(
+            "{}: recordings must be .gif, .png or .apng",
+            recording.output.display()
+        ))
+    })?;
+    let every = recording.every_n_frames.max(1);
+    AnimationEncoder::create(
+        &recording.output,
+        AnimationConfig {
+            format,
+            frame_delay: Duration::from_secs_f64(every as f64 / target_fps.max(1) as f64),
+            loop_mode: recording.loop_mode,
+            palette_size: recording.palette_size,
+        },
+    )
 }
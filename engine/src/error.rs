@@ -10,4 +10,14 @@ pub enum EngineError {
     RendererInit(String),
     #[error("runtime error: {0}")]
     Runtime(String),
+    #[error("image error: {0}")]
+    Image(String),
+    #[error("io error: {0}")]
+    Io(String),
+    #[error("scene error: {0}")]
+    Scene(String),
+    #[error("physics error: {0}")]
+    Physics(String),
+    #[error("asset error: {0}")]
+    Asset(String),
 }
@@ -0,0 +1,38 @@
+use crate::debug_draw::DebugDraw;
+use crate::engine::{EngineEvent, StopHandle};
+use crate::physics::{PhysicsEvent, PhysicsWorld};
+use crate::scene::Scene;
+
+pub struct EngineContext<'a> {
+    pub scene: &'a mut Scene,
+    pub physics: &'a mut PhysicsWorld,
+    // Events from every fixed step run so far this frame; physics.events() only
+    // holds the latest step's.
+    pub physics_events: &'a [PhysicsEvent],
+    pub debug_draw: &'a mut DebugDraw,
+    pub frame_index: u64,
+    pub(crate) stop: &'a StopHandle,
+}
+
+impl EngineContext<'_> {
+    pub fn request_stop(&self) {
+        self.stop.stop();
+    }
+}
+
+pub trait Game {
+    fn on_event(&mut self, event: &EngineEvent, ctx: &mut EngineContext<'_>);
+}
+
+impl Game for () {
+    fn on_event(&mut self, _event: &EngineEvent, _ctx: &mut EngineContext<'_>) {}
+}
+
+impl<F> Game for F
+where
+    F: FnMut(&EngineEvent, &mut EngineContext<'_>),
+{
+    fn on_event(&mut self, event: &EngineEvent, ctx: &mut EngineContext<'_>) {
+        self(event, ctx)
+    }
+}
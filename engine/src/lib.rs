@@ -1,8 +1,18 @@
+pub mod assets;
+pub mod debug_draw;
 pub mod engine;
 pub mod error;
+pub mod game;
+pub mod meme;
 pub mod physics;
 pub mod renderer;
 pub mod scene;
+pub mod text;
+pub mod time;
 
-pub use engine::{Engine, EngineConfig, EngineEvent, EngineResult};
+pub use engine::{
+    CaptureConfig, Engine, EngineConfig, EngineEvent, EngineResult, HeadlessConfig, RecordingConfig,
+    StopHandle,
+};
 pub use error::EngineError;
+pub use game::{EngineContext, Game};
@@ -0,0 +1,177 @@
+use crate::assets::Assets;
+use crate::error::EngineError;
+use crate::renderer::{
+    BackendKind, FilterMode, Image, RenderFrame, Renderer, Sampler, Sprite, SpriteBatcher, WrapMode,
+};
+use crate::text::{layout_text, Font, FontHandle, Text, TextRenderer, TextStyle};
+use glam::{Mat4, Vec2, Vec3, Vec4};
+
+// Smallest size a caption is shrunk to when a single word is wider than the image.
+const MIN_CAPTION_SIZE: f32 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CaptionPosition {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+    // Center of the caption block, normalized to the image (0,0 = top-left).
+    At(Vec2),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caption {
+    pub text: String,
+    pub position: CaptionPosition,
+    // None picks the classic outlined style scaled to the image.
+    pub style: Option<TextStyle>,
+}
+
+impl Caption {
+    pub fn new(text: impl Into<String>, position: CaptionPosition) -> Self {
+        Self {
+            text: text.into(),
+            position,
+            style: None,
+        }
+    }
+
+    pub fn top(text: impl Into<String>) -> Self {
+        Self::new(text, CaptionPosition::Top)
+    }
+
+    pub fn bottom(text: impl Into<String>) -> Self {
+        Self::new(text, CaptionPosition::Bottom)
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemeConfig {
+    pub render_backends: Vec<BackendKind>,
+    // Both as fractions of the image height.
+    pub caption_size: f32,
+    pub margin: f32,
+    pub background: Vec4,
+}
+
+impl Default for MemeConfig {
+    fn default() -> Self {
+        Self {
+            render_backends: BackendKind::default_fallback_order(),
+            caption_size: 0.1,
+            margin: 0.03,
+            background: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+}
+
+// Composites captions over a base image with the offscreen renderer, so it
+// works without a window.
+#[derive(Debug, Clone)]
+pub struct MemeCompositor {
+    font: Font,
+    config: MemeConfig,
+}
+
+impl MemeCompositor {
+    pub fn new(font: Font, config: MemeConfig) -> Self {
+        Self { font, config }
+    }
+
+    pub fn config(&self) -> &MemeConfig {
+        &self.config
+    }
+
+    pub fn default_style(&self, width: u32, height: u32) -> TextStyle {
+        let margin = height as f32 * self.config.margin;
+        TextStyle::meme((height as f32 * self.config.caption_size).max(MIN_CAPTION_SIZE))
+            .with_max_width((width as f32 - 2.0 * margin).max(1.0))
+    }
+
+    pub fn compose(&self, base: &Image, captions: &[Caption]) -> Result<Image, EngineError> {
+        let (width, height) = (base.width(), base.height());
+        if width == 0 || height == 0 {
+            return Err(EngineError::Image("meme base image is empty".to_string()));
+        }
+        let mut fonts = Assets::new();
+        let font = fonts.insert(self.font.clone());
+        let mut textures = Assets::new();
+        let base_texture = textures.insert(base.clone());
+
+        let mut batcher = SpriteBatcher::new();
+        let mut background = Sprite::new(base_texture, Vec2::new(width as f32, height as f32))
+            .with_pivot(Vec2::ZERO)
+            .with_z_order(-1);
+        background.sampler = Sampler::new(FilterMode::Nearest, WrapMode::Clamp);
+        batcher.push(background, Mat4::IDENTITY);
+
+        let texts: Vec<(Text, Mat4)> = captions
+            .iter()
+            .filter(|caption| !caption.text.trim().is_empty())
+            .map(|caption| self.caption_text(caption, font, width, height))
+            .collect();
+        TextRenderer::new().queue(
+            &fonts,
+            &mut textures,
+            texts.iter().map(|(text, transform)| (text, *transform)),
+            &mut batcher,
+        );
+
+        let mut renderer = Renderer::offscreen(width, height, &self.config.render_backends)?;
+        renderer.sync_textures(&textures)?;
+        renderer.render(&RenderFrame {
+            clear_color: self.config.background,
+            sprites: batcher.build(Mat4::IDENTITY),
+            ..RenderFrame::default()
+        })?;
+        renderer.capture_frame()
+    }
+
+    pub fn compose_png(&self, base: &Image, captions: &[Caption]) -> Result<Vec<u8>, EngineError> {
+        self.compose(base, captions)?.encode_png()
+    }
+
+    fn caption_text(
+        &self,
+        caption: &Caption,
+        font: FontHandle,
+        width: u32,
+        height: u32,
+    ) -> (Text, Mat4) {
+        let mut style = caption
+            .style
+            .unwrap_or_else(|| self.default_style(width, height));
+        // Shrink until the widest line fits, which only matters for long words.
+        if let Some(max_width) = style.max_width {
+            while style.size > MIN_CAPTION_SIZE
+                && layout_text(&self.font, &caption.text, &style).size.x > max_width
+            {
+                let size = (style.size * 0.9).max(MIN_CAPTION_SIZE);
+                if let Some(outline) = style.outline.as_mut() {
+                    outline.width *= size / style.size;
+                }
+                style.size = size;
+            }
+        }
+        let (width, height) = (width as f32, height as f32);
+        let margin = height * self.config.margin;
+        let (pivot, anchor) = match caption.position {
+            CaptionPosition::Top => (Vec2::new(0.5, 0.0), Vec2::new(width * 0.5, margin)),
+            CaptionPosition::Center => (Vec2::splat(0.5), Vec2::new(width, height) * 0.5),
+            CaptionPosition::Bottom => {
+                (Vec2::new(0.5, 1.0), Vec2::new(width * 0.5, height - margin))
+            }
+            CaptionPosition::At(position) => {
+                (Vec2::splat(0.5), position * Vec2::new(width, height))
+            }
+        };
+        let text = Text::new(caption.text.clone(), font, style).with_pivot(pivot);
+        let transform = Mat4::from_translation(Vec3::new(anchor.x, anchor.y, 0.0));
+        (text, transform)
+    }
+}
@@ -0,0 +1,282 @@
+use crate::error::EngineError;
+use crate::physics::convert::{to_isometry, to_point, to_vector};
+use crate::scene::Transform;
+use glam::{Quat, Vec3};
+use rapier3d::prelude as rapier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BodyHandle(pub(crate) rapier::RigidBodyHandle);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColliderHandle(pub(crate) rapier::ColliderHandle);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyKind {
+    Dynamic,
+    Kinematic,
+    KinematicVelocity,
+    Fixed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self {
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl Pose {
+    pub fn from_transform(transform: &Transform) -> Self {
+        Self {
+            position: transform.position,
+            rotation: transform.rotation,
+        }
+    }
+
+    pub fn apply_to(&self, transform: &mut Transform) {
+        transform.position = self.position;
+        transform.rotation = self.rotation;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BodyDesc {
+    pub kind: BodyKind,
+    pub pose: Pose,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+    pub gravity_scale: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub can_sleep: bool,
+    pub ccd: bool,
+}
+
+impl Default for BodyDesc {
+    fn default() -> Self {
+        Self {
+            kind: BodyKind::Dynamic,
+            pose: Pose::default(),
+            linear_velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            can_sleep: true,
+            ccd: false,
+        }
+    }
+}
+
+impl BodyDesc {
+    pub fn dynamic(position: Vec3) -> Self {
+        Self::with_kind(BodyKind::Dynamic, position)
+    }
+
+    pub fn kinematic(position: Vec3) -> Self {
+        Self::with_kind(BodyKind::Kinematic, position)
+    }
+
+    pub fn fixed(position: Vec3) -> Self {
+        Self::with_kind(BodyKind::Fixed, position)
+    }
+
+    fn with_kind(kind: BodyKind, position: Vec3) -> Self {
+        Self {
+            kind,
+            pose: Pose {
+                position,
+                ..Pose::default()
+            },
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ColliderShape {
+    Box {
+        half_extents: Vec3,
+    },
+    Sphere {
+        radius: f32,
+    },
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+    ConvexHull {
+        points: Vec<Vec3>,
+    },
+    TriMesh {
+        vertices: Vec<Vec3>,
+        indices: Vec<[u32; 3]>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionGroups {
+    pub memberships: u32,
+    pub filter: u32,
+}
+
+impl Default for CollisionGroups {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl CollisionGroups {
+    pub const ALL: Self = Self::new(u32::MAX, u32::MAX);
+    pub const NONE: Self = Self::new(0, 0);
+
+    pub const fn new(memberships: u32, filter: u32) -> Self {
+        Self {
+            memberships,
+            filter,
+        }
+    }
+
+    pub(crate) fn to_interaction_groups(self) -> rapier::InteractionGroups {
+        rapier::InteractionGroups::new(
+            rapier::Group::from_bits_truncate(self.memberships),
+            rapier::Group::from_bits_truncate(self.filter),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColliderDesc {
+    pub shape: ColliderShape,
+    pub offset: Pose,
+    pub density: f32,
+    pub friction: f32,
+    pub restitution: f32,
+    pub sensor: bool,
+    pub groups: CollisionGroups,
+    pub collision_events: bool,
+    pub contact_force_threshold: Option<f32>,
+}
+
+impl ColliderDesc {
+    pub fn new(shape: ColliderShape) -> Self {
+        Self {
+            shape,
+            offset: Pose::default(),
+            density: 1.0,
+            friction: 0.5,
+            restitution: 0.0,
+            sensor: false,
+            groups: CollisionGroups::default(),
+            collision_events: true,
+            contact_force_threshold: None,
+        }
+    }
+
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self::new(ColliderShape::Box { half_extents })
+    }
+
+    pub fn sphere(radius: f32) -> Self {
+        Self::new(ColliderShape::Sphere { radius })
+    }
+
+    pub fn capsule(half_height: f32, radius: f32) -> Self {
+        Self::new(ColliderShape::Capsule {
+            half_height,
+            radius,
+        })
+    }
+}
+
+// Marks bodies whose BodyDesc asked for CCD in the rapier user data, so turning
+// the global CCD default off leaves them alone.
+pub(crate) const BODY_CCD: u128 = 1;
+
+pub(crate) fn build_body(desc: &BodyDesc) -> rapier::RigidBody {
+    let builder = match desc.kind {
+        BodyKind::Dynamic => rapier::RigidBodyBuilder::dynamic(),
+        BodyKind::Kinematic => rapier::RigidBodyBuilder::kinematic_position_based(),
+        BodyKind::KinematicVelocity => rapier::RigidBodyBuilder::kinematic_velocity_based(),
+        BodyKind::Fixed => rapier::RigidBodyBuilder::fixed(),
+    };
+    builder
+        .position(to_isometry(desc.pose.position, desc.pose.rotation))
+        .linvel(to_vector(desc.linear_velocity))
+        .angvel(to_vector(desc.angular_velocity))
+        .gravity_scale(desc.gravity_scale)
+        .linear_damping(desc.linear_damping)
+        .angular_damping(desc.angular_damping)
+        .can_sleep(desc.can_sleep)
+        .ccd_enabled(desc.ccd)
+        .user_data(if desc.ccd { BODY_CCD } else { 0 })
+        .build()
+}
+
+pub(crate) fn build_shape(shape: &ColliderShape) -> Result<rapier::SharedShape, EngineError> {
+    Ok(match shape {
+        ColliderShape::Box { half_extents } => {
+            rapier::SharedShape::cuboid(half_extents.x, half_extents.y, half_extents.z)
+        }
+        ColliderShape::Sphere { radius } => rapier::SharedShape::ball(*radius),
+        ColliderShape::Capsule {
+            half_height,
+            radius,
+        } => rapier::SharedShape::capsule_y(*half_height, *radius),
+        ColliderShape::ConvexHull { points } => {
+            let points: Vec<_> = points.iter().copied().map(to_point).collect();
+            rapier::SharedShape::convex_hull(&points).ok_or_else(|| {
+                EngineError::Physics(
+                    "convex hull needs at least four non-coplanar points".to_string(),
+                )
+            })?
+        }
+        ColliderShape::TriMesh { vertices, indices } => {
+            if vertices.is_empty() || indices.is_empty() {
+                return Err(EngineError::Physics(
+                    "triangle mesh collider is empty".to_string(),
+                ));
+            }
+            if let Some(index) = indices
+                .iter()
+                .flatten()
+                .find(|&&index| index as usize >= vertices.len())
+            {
+                return Err(EngineError::Physics(format!(
+                    "triangle mesh index {index} out of range for {} vertices",
+                    vertices.len()
+                )));
+            }
+            let vertices = vertices.iter().copied().map(to_point).collect();
+            rapier::SharedShape::trimesh(vertices, indices.clone())
+        }
+    })
+}
+
+pub(crate) fn build_collider(desc: &ColliderDesc) -> Result<rapier::ColliderBuilder, EngineError> {
+    let builder = rapier::ColliderBuilder::new(build_shape(&desc.shape)?);
+    let mut events = rapier::ActiveEvents::empty();
+    if desc.collision_events {
+        events |= rapier::ActiveEvents::COLLISION_EVENTS;
+    }
+    if desc.contact_force_threshold.is_some() {
+        events |= rapier::ActiveEvents::CONTACT_FORCE_EVENTS;
+    }
+    Ok(builder
+        .position(to_isometry(desc.offset.position, desc.offset.rotation))
+        .density(desc.density)
+        .friction(desc.friction)
+        .restitution(desc.restitution)
+        .sensor(desc.sensor)
+        .collision_groups(desc.groups.to_interaction_groups())
+        .active_events(events)
+        .contact_force_event_threshold(desc.contact_force_threshold.unwrap_or(0.0)))
+}
@@ -0,0 +1,227 @@
+use crate::error::EngineError;
+use crate::physics::body::build_shape;
+use crate::physics::convert::{from_vector, to_isometry, to_point, to_vector};
+use crate::physics::{
+    BodyDesc, BodyHandle, ColliderDesc, ColliderHandle, ColliderShape, PhysicsWorld, Pose,
+};
+use glam::{Quat, Vec3};
+use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+use rapier3d::na::Unit;
+use rapier3d::prelude as rapier;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterConfig {
+    pub up: Vec3,
+    pub skin_width: f32,
+    pub max_step_height: Option<f32>,
+    pub min_step_width: f32,
+    pub max_slope_climb_angle: f32,
+    pub min_slope_slide_angle: f32,
+    pub snap_to_ground: Option<f32>,
+    pub apply_gravity: bool,
+    pub ride_platforms: bool,
+}
+
+impl Default for CharacterConfig {
+    fn default() -> Self {
+        Self {
+            up: Vec3::Y,
+            skin_width: 0.02,
+            max_step_height: Some(0.3),
+            min_step_width: 0.2,
+            max_slope_climb_angle: 45f32.to_radians(),
+            min_slope_slide_angle: 30f32.to_radians(),
+            snap_to_ground: Some(0.2),
+            apply_gravity: true,
+            ride_platforms: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CharacterState {
+    pub grounded: bool,
+    pub sliding_down_slope: bool,
+    pub ground: Option<ColliderHandle>,
+    pub ground_normal: Option<Vec3>,
+    pub velocity: Vec3,
+}
+
+#[derive(Debug, Clone)]
+pub struct CharacterController {
+    pub config: CharacterConfig,
+    body: BodyHandle,
+    collider: ColliderHandle,
+    shape: rapier::SharedShape,
+    position: Vec3,
+    fall_velocity: Vec3,
+    state: CharacterState,
+}
+
+impl CharacterController {
+    pub fn body(&self) -> BodyHandle {
+        self.body
+    }
+
+    pub fn collider(&self) -> ColliderHandle {
+        self.collider
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    pub fn state(&self) -> CharacterState {
+        self.state
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.state.grounded
+    }
+
+    pub fn jump(&mut self, speed: f32) {
+        self.fall_velocity = self.config.up.normalize_or_zero() * speed;
+        self.state.grounded = false;
+        self.state.ground = None;
+        self.state.ground_normal = None;
+    }
+
+    fn rapier_controller(&self) -> KinematicCharacterController {
+        let config = &self.config;
+        KinematicCharacterController {
+            up: Unit::try_new(to_vector(config.up), 1.0e-6).unwrap_or(rapier::Vector::y_axis()),
+            offset: CharacterLength::Absolute(config.skin_width),
+            slide: true,
+            autostep: config.max_step_height.map(|height| CharacterAutostep {
+                max_height: CharacterLength::Absolute(height),
+                min_width: CharacterLength::Absolute(config.min_step_width),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: config.max_slope_climb_angle,
+            min_slope_slide_angle: config.min_slope_slide_angle,
+            snap_to_ground: config.snap_to_ground.map(CharacterLength::Absolute),
+        }
+    }
+}
+
+impl PhysicsWorld {
+    pub fn create_character(
+        &mut self,
+        position: Vec3,
+        shape: ColliderShape,
+        config: CharacterConfig,
+    ) -> Result<CharacterController, EngineError> {
+        let collider_shape = build_shape(&shape)?;
+        let body = self.create_body(&BodyDesc {
+            can_sleep: false,
+            ..BodyDesc::kinematic(position)
+        });
+        let collider = self.add_collider(body, &ColliderDesc::new(shape))?;
+        Ok(CharacterController {
+            config,
+            body,
+            collider,
+            shape: collider_shape,
+            position,
+            fall_velocity: Vec3::ZERO,
+            state: CharacterState::default(),
+        })
+    }
+
+    pub fn move_character(
+        &mut self,
+        character: &mut CharacterController,
+        desired_velocity: Vec3,
+        delta_seconds: f32,
+    ) -> CharacterState {
+        if delta_seconds <= 0.0 {
+            return character.state;
+        }
+        let up = character.config.up.normalize_or_zero();
+        if character.config.apply_gravity {
+            character.fall_velocity += from_vector(&self.gravity) * delta_seconds;
+        }
+        let mut velocity = desired_velocity + character.fall_velocity;
+        if character.config.ride_platforms {
+            velocity += self.platform_velocity(character.state.ground, character.position);
+        }
+
+        let shape = character.shape.clone();
+        let filter = rapier::QueryFilter::new()
+            .exclude_rigid_body(character.body.0)
+            .exclude_sensors();
+        let movement = character.rapier_controller().move_shape(
+            delta_seconds,
+            &self.bodies,
+            &self.colliders,
+            &self.query_pipeline,
+            shape.as_ref(),
+            &to_isometry(character.position, Quat::IDENTITY),
+            to_vector(velocity * delta_seconds),
+            filter,
+            |_| {},
+        );
+        let translation = from_vector(&movement.translation);
+        character.position += translation;
+        if movement.grounded && character.fall_velocity.dot(up) <= 0.0 {
+            character.fall_velocity = Vec3::ZERO;
+        }
+
+        let ground = if movement.grounded {
+            self.find_ground(character, shape.as_ref(), filter)
+        } else {
+            None
+        };
+        character.state = CharacterState {
+            grounded: movement.grounded,
+            sliding_down_slope: movement.is_sliding_down_slope,
+            ground: ground.map(|(collider, _)| collider),
+            ground_normal: ground.map(|(_, normal)| normal),
+            velocity: translation / delta_seconds,
+        };
+        self.set_kinematic_target(
+            character.body,
+            Pose {
+                position: character.position,
+                ..Pose::default()
+            },
+        );
+        character.state
+    }
+
+    fn platform_velocity(&self, ground: Option<ColliderHandle>, position: Vec3) -> Vec3 {
+        let Some(body) = ground
+            .and_then(|ground| self.collider_body(ground))
+            .and_then(|body| self.bodies.get(body.0))
+        else {
+            return Vec3::ZERO;
+        };
+        // Rapier already drags the character along kinematic platforms.
+        if !body.is_dynamic() {
+            return Vec3::ZERO;
+        }
+        from_vector(&body.velocity_at_point(&to_point(position)))
+    }
+
+    fn find_ground(
+        &self,
+        character: &CharacterController,
+        shape: &dyn rapier::Shape,
+        filter: rapier::QueryFilter,
+    ) -> Option<(ColliderHandle, Vec3)> {
+        let down = -character.config.up.normalize_or_zero();
+        let probe =
+            character.config.skin_width * 2.0 + character.config.snap_to_ground.unwrap_or(0.0);
+        let (handle, toi) = self.query_pipeline.cast_shape(
+            &self.bodies,
+            &self.colliders,
+            &to_isometry(character.position, Quat::IDENTITY),
+            &to_vector(down),
+            shape,
+            probe.max(0.05),
+            true,
+            filter,
+        )?;
+        Some((ColliderHandle(handle), from_vector(&toi.normal1)))
+    }
+}
@@ -0,0 +1,138 @@
+use crate::physics::body::BODY_CCD;
+use crate::physics::convert::to_vector;
+use crate::physics::PhysicsWorld;
+use glam::Vec3;
+use rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhysicsConfig {
+    pub gravity: Vec3,
+    pub solver_iterations: usize,
+    pub friction_iterations: usize,
+    pub ccd: bool,
+    pub ccd_substeps: usize,
+    pub substeps: u32,
+    pub sleep_linear_threshold: f32,
+    pub sleep_angular_threshold: f32,
+    pub time_until_sleep: f32,
+    pub length_unit: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            solver_iterations: 4,
+            friction_iterations: 4,
+            ccd: false,
+            ccd_substeps: 1,
+            substeps: 1,
+            sleep_linear_threshold: 0.4,
+            sleep_angular_threshold: 0.5,
+            time_until_sleep: 2.0,
+            length_unit: 1.0,
+        }
+    }
+}
+
+impl PhysicsWorld {
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: PhysicsConfig) {
+        let ccd_changed = config.ccd != self.config.ccd;
+        let sleep_changed = config.sleep_linear_threshold != self.config.sleep_linear_threshold
+            || config.sleep_angular_threshold != self.config.sleep_angular_threshold
+            || config.time_until_sleep != self.config.time_until_sleep;
+        self.config = config;
+        self.gravity = to_vector(config.gravity);
+        self.integration_parameters = integration_parameters(&config);
+        for (_, body) in self.bodies.iter_mut() {
+            if ccd_changed && body.is_dynamic() {
+                body.enable_ccd(config.ccd || body.user_data & BODY_CCD != 0);
+            }
+            if sleep_changed {
+                apply_sleep_thresholds(body, &config);
+            }
+        }
+    }
+
+    pub fn gravity(&self) -> Vec3 {
+        self.config.gravity
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vec3) {
+        self.set_config(PhysicsConfig {
+            gravity,
+            ..self.config
+        });
+        for (_, body) in self.bodies.iter_mut() {
+            body.wake_up(true);
+        }
+    }
+
+    pub fn set_solver_iterations(&mut self, iterations: usize) {
+        self.set_config(PhysicsConfig {
+            solver_iterations: iterations,
+            ..self.config
+        });
+    }
+
+    pub fn set_ccd_enabled(&mut self, enabled: bool) {
+        self.set_config(PhysicsConfig {
+            ccd: enabled,
+            ..self.config
+        });
+    }
+
+    pub fn set_substeps(&mut self, substeps: u32) {
+        self.set_config(PhysicsConfig {
+            substeps,
+            ..self.config
+        });
+    }
+
+    pub fn set_sleep_thresholds(&mut self, linear: f32, angular: f32, time_until_sleep: f32) {
+        self.set_config(PhysicsConfig {
+            sleep_linear_threshold: linear,
+            sleep_angular_threshold: angular,
+            time_until_sleep,
+            ..self.config
+        });
+    }
+
+    pub fn set_length_unit(&mut self, length_unit: f32) {
+        self.set_config(PhysicsConfig {
+            length_unit,
+            ..self.config
+        });
+    }
+}
+
+pub(crate) fn integration_parameters(config: &PhysicsConfig) -> IntegrationParameters {
+    let defaults = IntegrationParameters::default();
+    let length_unit = config.length_unit.max(f32::EPSILON);
+    IntegrationParameters {
+        num_solver_iterations: NonZeroUsize::new(config.solver_iterations)
+            .unwrap_or(NonZeroUsize::MIN),
+        num_additional_friction_iterations: config.friction_iterations,
+        max_ccd_substeps: config.ccd_substeps.max(1),
+        allowed_linear_error: defaults.allowed_linear_error * length_unit,
+        prediction_distance: defaults.prediction_distance * length_unit,
+        ..defaults
+    }
+}
+
+pub(crate) fn apply_sleep_thresholds(body: &mut RigidBody, config: &PhysicsConfig) {
+    let activation = body.activation_mut();
+    // Bodies created with `can_sleep: false` carry a negative threshold.
+    if activation.linear_threshold < 0.0 {
+        return;
+    }
+    activation.linear_threshold = config.sleep_linear_threshold;
+    activation.angular_threshold = config.sleep_angular_threshold;
+    activation.time_until_sleep = config.time_until_sleep;
+}
@@ -0,0 +1,35 @@
+use crate::physics::Pose;
+use glam::{Quat, Vec3};
+use rapier3d::na::{Quaternion, UnitQuaternion};
+use rapier3d::prelude::{Isometry, Point, Real, Rotation, Vector};
+
+pub(crate) fn to_vector(v: Vec3) -> Vector<Real> {
+    Vector::new(v.x, v.y, v.z)
+}
+
+pub(crate) fn from_vector(v: &Vector<Real>) -> Vec3 {
+    Vec3::new(v.x, v.y, v.z)
+}
+
+pub(crate) fn to_point(v: Vec3) -> Point<Real> {
+    Point::new(v.x, v.y, v.z)
+}
+
+pub(crate) fn to_rotation(q: Quat) -> Rotation<Real> {
+    UnitQuaternion::new_normalize(Quaternion::new(q.w, q.x, q.y, q.z))
+}
+
+pub(crate) fn from_rotation(r: &Rotation<Real>) -> Quat {
+    Quat::from_xyzw(r.i, r.j, r.k, r.w)
+}
+
+pub(crate) fn to_isometry(position: Vec3, rotation: Quat) -> Isometry<Real> {
+    Isometry::from_parts(to_vector(position).into(), to_rotation(rotation))
+}
+
+pub(crate) fn to_pose(isometry: &Isometry<Real>) -> Pose {
+    Pose {
+        position: from_vector(&isometry.translation.vector),
+        rotation: from_rotation(&isometry.rotation),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::debug_draw::DebugDraw;
+use crate::physics::convert::from_vector;
+use crate::physics::PhysicsWorld;
+use glam::{Vec3, Vec4};
+use rapier3d::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicsDebugOptions {
+    pub colliders: bool,
+    pub contacts: bool,
+    pub joints: bool,
+    pub aabbs: bool,
+    pub body_axes: bool,
+}
+
+impl Default for PhysicsDebugOptions {
+    fn default() -> Self {
+        Self {
+            colliders: true,
+            contacts: true,
+            joints: true,
+            aabbs: false,
+            body_axes: false,
+        }
+    }
+}
+
+impl PhysicsDebugOptions {
+    fn mode(&self) -> DebugRenderMode {
+        let mut mode = DebugRenderMode::empty();
+        mode.set(DebugRenderMode::COLLIDER_SHAPES, self.colliders);
+        mode.set(DebugRenderMode::CONTACTS, self.contacts);
+        mode.set(DebugRenderMode::JOINTS, self.joints);
+        mode.set(DebugRenderMode::COLLIDER_AABBS, self.aabbs);
+        mode.set(DebugRenderMode::RIGID_BODY_AXES, self.body_axes);
+        mode
+    }
+}
+
+struct LineCollector<'a> {
+    draw: &'a mut DebugDraw,
+}
+
+impl DebugRenderBackend for LineCollector<'_> {
+    fn draw_line(
+        &mut self,
+        _object: DebugRenderObject,
+        a: Point<Real>,
+        b: Point<Real>,
+        color: [f32; 4],
+    ) {
+        self.draw.line(
+            from_vector(&a.coords),
+            from_vector(&b.coords),
+            hsla_to_rgba(color),
+        );
+    }
+}
+
+impl PhysicsWorld {
+    pub fn debug_draw(&mut self, options: &PhysicsDebugOptions, draw: &mut DebugDraw) {
+        let pipeline = self
+            .debug_pipeline
+            .get_or_insert_with(DebugRenderPipeline::default);
+        pipeline.mode = options.mode();
+        pipeline.render(
+            &mut LineCollector { draw },
+            &self.bodies,
+            &self.colliders,
+            &self.impulse_joints,
+            &self.multibody_joints,
+            &self.narrow_phase,
+        );
+    }
+}
+
+// Rapier's debug style stores colors as HSLA with the hue in degrees.
+fn hsla_to_rgba([hue, saturation, lightness, alpha]: [f32; 4]) -> Vec4 {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let rgb = match sector as u32 {
+        0 => Vec3::new(chroma, x, 0.0),
+        1 => Vec3::new(x, chroma, 0.0),
+        2 => Vec3::new(0.0, chroma, x),
+        3 => Vec3::new(0.0, x, chroma),
+        4 => Vec3::new(x, 0.0, chroma),
+        _ => Vec3::new(chroma, 0.0, x),
+    };
+    (rgb + Vec3::splat(lightness - chroma * 0.5)).extend(alpha)
+}
@@ -0,0 +1,135 @@
+use crate::physics::convert::from_vector;
+use crate::physics::ColliderHandle;
+use glam::Vec3;
+use rapier3d::crossbeam::channel::{unbounded, Receiver};
+use rapier3d::prelude as rapier;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhysicsEvent {
+    CollisionStarted {
+        a: ColliderHandle,
+        b: ColliderHandle,
+    },
+    CollisionStopped {
+        a: ColliderHandle,
+        b: ColliderHandle,
+        removed: bool,
+    },
+    SensorEntered {
+        sensor: ColliderHandle,
+        other: ColliderHandle,
+    },
+    SensorExited {
+        sensor: ColliderHandle,
+        other: ColliderHandle,
+        removed: bool,
+    },
+    ContactForce {
+        a: ColliderHandle,
+        b: ColliderHandle,
+        total_force: Vec3,
+        total_force_magnitude: f32,
+        max_force_direction: Vec3,
+        max_force_magnitude: f32,
+    },
+}
+
+impl PhysicsEvent {
+    pub fn involves(&self, collider: ColliderHandle) -> bool {
+        let (a, b) = self.colliders();
+        a == collider || b == collider
+    }
+
+    pub fn colliders(&self) -> (ColliderHandle, ColliderHandle) {
+        match *self {
+            Self::CollisionStarted { a, b }
+            | Self::CollisionStopped { a, b, .. }
+            | Self::ContactForce { a, b, .. } => (a, b),
+            Self::SensorEntered { sensor, other } | Self::SensorExited { sensor, other, .. } => {
+                (sensor, other)
+            }
+        }
+    }
+}
+
+pub(crate) struct EventQueue {
+    pub(crate) collector: rapier::ChannelEventCollector,
+    collisions: Receiver<rapier::CollisionEvent>,
+    contact_forces: Receiver<rapier::ContactForceEvent>,
+}
+
+impl EventQueue {
+    pub(crate) fn new() -> Self {
+        let (collision_send, collisions) = unbounded();
+        let (contact_force_send, contact_forces) = unbounded();
+        Self {
+            collector: rapier::ChannelEventCollector::new(collision_send, contact_force_send),
+            collisions,
+            contact_forces,
+        }
+    }
+
+    pub(crate) fn drain_into(&self, colliders: &rapier::ColliderSet, out: &mut Vec<PhysicsEvent>) {
+        while let Ok(event) = self.collisions.try_recv() {
+            out.push(convert_collision(event, colliders));
+        }
+        while let Ok(event) = self.contact_forces.try_recv() {
+            out.push(PhysicsEvent::ContactForce {
+                a: ColliderHandle(event.collider1),
+                b: ColliderHandle(event.collider2),
+                total_force: from_vector(&event.total_force),
+                total_force_magnitude: event.total_force_magnitude,
+                max_force_direction: from_vector(&event.max_force_direction),
+                max_force_magnitude: event.max_force_magnitude,
+            });
+        }
+    }
+}
+
+fn convert_collision(
+    event: rapier::CollisionEvent,
+    colliders: &rapier::ColliderSet,
+) -> PhysicsEvent {
+    let (a, b, flags) = match event {
+        rapier::CollisionEvent::Started(a, b, flags)
+        | rapier::CollisionEvent::Stopped(a, b, flags) => (a, b, flags),
+    };
+    let removed = flags.contains(rapier::CollisionEventFlags::REMOVED);
+    if !flags.contains(rapier::CollisionEventFlags::SENSOR) {
+        return if event.started() {
+            PhysicsEvent::CollisionStarted {
+                a: ColliderHandle(a),
+                b: ColliderHandle(b),
+            }
+        } else {
+            PhysicsEvent::CollisionStopped {
+                a: ColliderHandle(a),
+                b: ColliderHandle(b),
+                removed,
+            }
+        };
+    }
+    let is_sensor =
+        |handle: rapier::ColliderHandle| colliders.get(handle).map(|collider| collider.is_sensor());
+    let b_is_sensor = match (is_sensor(a), is_sensor(b)) {
+        (Some(true), _) => false,
+        (_, Some(true)) => true,
+        // A removed collider is gone from the set; if the survivor isn't the
+        // sensor, the removed one was.
+        (Some(false), None) => removed,
+        _ => false,
+    };
+    let (sensor, other) = if b_is_sensor { (b, a) } else { (a, b) };
+    if event.started() {
+        PhysicsEvent::SensorEntered {
+            sensor: ColliderHandle(sensor),
+            other: ColliderHandle(other),
+        }
+    } else {
+        PhysicsEvent::SensorExited {
+            sensor: ColliderHandle(sensor),
+            other: ColliderHandle(other),
+            removed,
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::error::EngineError;
+use crate::physics::convert::{to_point, to_vector};
+use crate::physics::{BodyHandle, PhysicsWorld};
+use glam::Vec3;
+use rapier3d::na::Unit;
+use rapier3d::prelude as rapier;
+use rapier3d::prelude::{JointAxesMask, JointAxis};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JointHandle(pub(crate) rapier::ImpulseJointHandle);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointKind {
+    Fixed,
+    Revolute {
+        axis: Vec3,
+    },
+    Prismatic {
+        axis: Vec3,
+    },
+    Spherical,
+    Rope {
+        max_distance: f32,
+    },
+    Spring {
+        rest_length: f32,
+        stiffness: f32,
+        damping: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointMotor {
+    pub target_position: f32,
+    pub target_velocity: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+    pub max_force: f32,
+}
+
+impl JointMotor {
+    pub fn velocity(target_velocity: f32, factor: f32) -> Self {
+        Self {
+            target_position: 0.0,
+            target_velocity,
+            stiffness: 0.0,
+            damping: factor,
+            max_force: f32::MAX,
+        }
+    }
+
+    pub fn position(target_position: f32, stiffness: f32, damping: f32) -> Self {
+        Self {
+            target_position,
+            target_velocity: 0.0,
+            stiffness,
+            damping,
+            max_force: f32::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointDesc {
+    pub kind: JointKind,
+    pub anchor1: Vec3,
+    pub anchor2: Vec3,
+    pub limits: Option<[f32; 2]>,
+    pub motor: Option<JointMotor>,
+    pub contacts_enabled: bool,
+}
+
+impl JointDesc {
+    pub fn new(kind: JointKind) -> Self {
+        Self {
+            kind,
+            anchor1: Vec3::ZERO,
+            anchor2: Vec3::ZERO,
+            limits: None,
+            motor: None,
+            contacts_enabled: false,
+        }
+    }
+
+    pub fn fixed() -> Self {
+        Self::new(JointKind::Fixed)
+    }
+
+    pub fn revolute(axis: Vec3) -> Self {
+        Self::new(JointKind::Revolute { axis })
+    }
+
+    pub fn prismatic(axis: Vec3) -> Self {
+        Self::new(JointKind::Prismatic { axis })
+    }
+
+    pub fn spherical() -> Self {
+        Self::new(JointKind::Spherical)
+    }
+
+    pub fn rope(max_distance: f32) -> Self {
+        Self::new(JointKind::Rope { max_distance })
+    }
+
+    pub fn spring(rest_length: f32, stiffness: f32, damping: f32) -> Self {
+        Self::new(JointKind::Spring {
+            rest_length,
+            stiffness,
+            damping,
+        })
+    }
+
+    pub fn with_anchors(mut self, anchor1: Vec3, anchor2: Vec3) -> Self {
+        self.anchor1 = anchor1;
+        self.anchor2 = anchor2;
+        self
+    }
+}
+
+impl PhysicsWorld {
+    pub fn create_joint(
+        &mut self,
+        body1: BodyHandle,
+        body2: BodyHandle,
+        desc: &JointDesc,
+    ) -> Result<JointHandle, EngineError> {
+        for body in [body1, body2] {
+            if !self.bodies.contains(body.0) {
+                return Err(EngineError::Physics(format!("unknown body {body:?}")));
+            }
+        }
+        let joint = build_joint(desc)?;
+        Ok(JointHandle(
+            self.impulse_joints.insert(body1.0, body2.0, joint, true),
+        ))
+    }
+
+    pub fn remove_joint(&mut self, handle: JointHandle) -> bool {
+        self.impulse_joints.remove(handle.0, true).is_some()
+    }
+
+    pub fn contains_joint(&self, handle: JointHandle) -> bool {
+        self.impulse_joints.contains(handle.0)
+    }
+
+    pub fn joint_count(&self) -> usize {
+        self.impulse_joints.len()
+    }
+
+    pub fn joint_bodies(&self, handle: JointHandle) -> Option<(BodyHandle, BodyHandle)> {
+        let joint = self.impulse_joints.get(handle.0)?;
+        Some((BodyHandle(joint.body1), BodyHandle(joint.body2)))
+    }
+
+    pub fn set_joint_motor(&mut self, handle: JointHandle, motor: Option<JointMotor>) -> bool {
+        let Some(joint) = self.impulse_joints.get_mut(handle.0) else {
+            return false;
+        };
+        apply_motor(&mut joint.data, motor);
+        self.wake_joint_bodies(handle);
+        true
+    }
+
+    pub fn set_joint_limits(&mut self, handle: JointHandle, limits: Option<[f32; 2]>) -> bool {
+        let Some(joint) = self.impulse_joints.get_mut(handle.0) else {
+            return false;
+        };
+        apply_limits(&mut joint.data, limits);
+        self.wake_joint_bodies(handle);
+        true
+    }
+
+    fn wake_joint_bodies(&mut self, handle: JointHandle) {
+        let Some((body1, body2)) = self.joint_bodies(handle) else {
+            return;
+        };
+        for body in [body1, body2] {
+            if let Some(body) = self.bodies.get_mut(body.0) {
+                body.wake_up(true);
+            }
+        }
+    }
+}
+
+fn build_joint(desc: &JointDesc) -> Result<rapier::GenericJoint, EngineError> {
+    let mut joint: rapier::GenericJoint = match desc.kind {
+        JointKind::Fixed => rapier::FixedJointBuilder::new().into(),
+        JointKind::Revolute { axis } => rapier::RevoluteJointBuilder::new(unit_axis(axis)?).into(),
+        JointKind::Prismatic { axis } => {
+            rapier::PrismaticJointBuilder::new(unit_axis(axis)?).into()
+        }
+        JointKind::Spherical => rapier::SphericalJointBuilder::new().into(),
+        JointKind::Rope { max_distance } => rapier::RopeJointBuilder::new(max_distance).into(),
+        JointKind::Spring {
+            rest_length,
+            stiffness,
+            damping,
+        } => rapier::SpringJointBuilder::new(rest_length, stiffness, damping).into(),
+    };
+    joint
+        .set_local_anchor1(to_point(desc.anchor1))
+        .set_local_anchor2(to_point(desc.anchor2))
+        .set_contacts_enabled(desc.contacts_enabled);
+    apply_limits(&mut joint, desc.limits);
+    apply_motor(&mut joint, desc.motor);
+    Ok(joint)
+}
+
+fn unit_axis(axis: Vec3) -> Result<rapier::UnitVector<rapier::Real>, EngineError> {
+    Unit::try_new(to_vector(axis), 1.0e-6)
+        .ok_or_else(|| EngineError::Physics(format!("joint axis {axis} has zero length")))
+}
+
+fn apply_limits(joint: &mut rapier::GenericJoint, limits: Option<[f32; 2]>) {
+    for &axis in free_axes(joint.locked_axes) {
+        match limits {
+            Some(limits) => {
+                joint.set_limits(axis, limits);
+            }
+            None => {
+                joint.limit_axes.remove(axis.into());
+            }
+        }
+    }
+}
+
+fn apply_motor(joint: &mut rapier::GenericJoint, motor: Option<JointMotor>) {
+    for &axis in free_axes(joint.locked_axes) {
+        match motor {
+            Some(motor) => {
+                joint
+                    .set_motor(
+                        axis,
+                        motor.target_position,
+                        motor.target_velocity,
+                        motor.stiffness,
+                        motor.damping,
+                    )
+                    .set_motor_max_force(axis, motor.max_force);
+            }
+            None => {
+                joint.motor_axes.remove(axis.into());
+            }
+        }
+    }
+}
+
+// Rope and spring joints drive their own limits and motors, so only the
+// hinge, slider and ball joints expose them.
+fn free_axes(locked: JointAxesMask) -> &'static [JointAxis] {
+    if locked == JointAxesMask::LOCKED_REVOLUTE_AXES {
+        &[JointAxis::AngX]
+    } else if locked == JointAxesMask::LOCKED_PRISMATIC_AXES {
+        &[JointAxis::X]
+    } else if locked == JointAxesMask::LOCKED_SPHERICAL_AXES {
+        &[JointAxis::AngX, JointAxis::AngY, JointAxis::AngZ]
+    } else {
+        &[]
+    }
+}
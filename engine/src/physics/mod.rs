@@ -1,6 +1,37 @@
+mod body;
+mod character;
+mod config;
+mod convert;
+mod debug;
+mod events;
+mod joint;
+mod query;
+mod snapshot;
+mod sync;
+
+use crate::error::EngineError;
+use body::{build_body, build_collider};
+use config::{apply_sleep_thresholds, integration_parameters};
+use convert::{from_vector, to_isometry, to_pose, to_vector};
+use events::EventQueue;
+use glam::Vec3;
 use rapier3d::prelude::*;
 
+pub use body::{
+    BodyDesc, BodyHandle, BodyKind, ColliderDesc, ColliderHandle, ColliderShape, CollisionGroups,
+    Pose,
+};
+pub use character::{CharacterConfig, CharacterController, CharacterState};
+pub use config::PhysicsConfig;
+pub use debug::PhysicsDebugOptions;
+pub use events::PhysicsEvent;
+pub use joint::{JointDesc, JointHandle, JointKind, JointMotor};
+pub use query::{PointHit, QueryFilter, RayHit, ShapeHit};
+pub use snapshot::PhysicsSnapshot;
+pub use sync::{pull_body_poses, push_kinematic_targets, RigidBody};
+
 pub struct PhysicsWorld {
+    config: PhysicsConfig,
     pipeline: PhysicsPipeline,
     gravity: Vector<Real>,
     integration_parameters: IntegrationParameters,
@@ -12,15 +43,31 @@ pub struct PhysicsWorld {
     impulse_joints: ImpulseJointSet,
     multibody_joints: MultibodyJointSet,
     ccd_solver: CCDSolver,
+    // Refreshed by step and by every mutator that adds, removes or teleports
+    // colliders, so &self queries never see stale shapes.
     query_pipeline: QueryPipeline,
+    event_queue: EventQueue,
+    events: Vec<PhysicsEvent>,
+    debug_pipeline: Option<DebugRenderPipeline>,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PhysicsWorld {
     pub fn new() -> Self {
+        Self::with_config(PhysicsConfig::default())
+    }
+
+    pub fn with_config(config: PhysicsConfig) -> Self {
         Self {
+            config,
             pipeline: PhysicsPipeline::new(),
-            gravity: vector![0.0, -9.81, 0.0],
-            integration_parameters: IntegrationParameters::default(),
+            gravity: to_vector(config.gravity),
+            integration_parameters: integration_parameters(&config),
             islands: IslandManager::new(),
             broad_phase: BroadPhase::new(),
             narrow_phase: NarrowPhase::new(),
@@ -30,29 +77,202 @@ impl PhysicsWorld {
             multibody_joints: MultibodyJointSet::new(),
             ccd_solver: CCDSolver::new(),
             query_pipeline: QueryPipeline::new(),
+            event_queue: EventQueue::new(),
+            events: Vec::new(),
+            debug_pipeline: None,
         }
     }
 
     pub fn step(&mut self, delta_seconds: f32) {
-        self.integration_parameters.dt = delta_seconds;
+        let substeps = self.config.substeps.max(1);
+        self.integration_parameters.dt = delta_seconds / substeps as f32;
+        self.integration_parameters.min_ccd_dt = self.integration_parameters.dt / 100.0;
         let hooks = ();
-        let events = ();
-        self.pipeline.step(
-            &self.gravity,
-            &self.integration_parameters,
-            &mut self.islands,
-            &mut self.broad_phase,
-            &mut self.narrow_phase,
-            &mut self.bodies,
-            &mut self.colliders,
-            &mut self.impulse_joints,
-            &mut self.multibody_joints,
-            &mut self.ccd_solver,
-            Some(&mut self.query_pipeline),
-            &hooks,
-            &events,
-        );
-        self.query_pipeline
-            .update(&self.bodies, &self.colliders);
+        self.events.clear();
+        for _ in 0..substeps {
+            self.pipeline.step(
+                &self.gravity,
+                &self.integration_parameters,
+                &mut self.islands,
+                &mut self.broad_phase,
+                &mut self.narrow_phase,
+                &mut self.bodies,
+                &mut self.colliders,
+                &mut self.impulse_joints,
+                &mut self.multibody_joints,
+                &mut self.ccd_solver,
+                Some(&mut self.query_pipeline),
+                &hooks,
+                &self.event_queue.collector,
+            );
+        }
+        self.update_queries();
+        self.event_queue
+            .drain_into(&self.colliders, &mut self.events);
+    }
+
+    // Events from the most recent step only.
+    pub fn events(&self) -> impl Iterator<Item = &PhysicsEvent> + '_ {
+        self.events.iter()
+    }
+
+    pub fn create_body(&mut self, desc: &BodyDesc) -> BodyHandle {
+        let mut body = build_body(desc);
+        if self.config.ccd && body.is_dynamic() {
+            body.enable_ccd(true);
+        }
+        apply_sleep_thresholds(&mut body, &self.config);
+        BodyHandle(self.bodies.insert(body))
+    }
+
+    pub fn remove_body(&mut self, handle: BodyHandle) -> bool {
+        let removed = self
+            .bodies
+            .remove(
+                handle.0,
+                &mut self.islands,
+                &mut self.colliders,
+                &mut self.impulse_joints,
+                &mut self.multibody_joints,
+                true,
+            )
+            .is_some();
+        self.update_queries();
+        removed
+    }
+
+    pub fn add_collider(
+        &mut self,
+        body: BodyHandle,
+        desc: &ColliderDesc,
+    ) -> Result<ColliderHandle, EngineError> {
+        if !self.bodies.contains(body.0) {
+            return Err(EngineError::Physics(format!("unknown body {body:?}")));
+        }
+        let collider = build_collider(desc)?;
+        let handle = self
+            .colliders
+            .insert_with_parent(collider, body.0, &mut self.bodies);
+        self.update_queries();
+        Ok(ColliderHandle(handle))
+    }
+
+    pub fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
+        let removed = self
+            .colliders
+            .remove(handle.0, &mut self.islands, &mut self.bodies, true)
+            .is_some();
+        self.update_queries();
+        removed
+    }
+
+    pub fn collider_body(&self, handle: ColliderHandle) -> Option<BodyHandle> {
+        self.colliders.get(handle.0)?.parent().map(BodyHandle)
+    }
+
+    pub fn body_colliders(&self, handle: BodyHandle) -> Vec<ColliderHandle> {
+        self.bodies
+            .get(handle.0)
+            .map(|body| {
+                body.colliders()
+                    .iter()
+                    .copied()
+                    .map(ColliderHandle)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn contains_body(&self, handle: BodyHandle) -> bool {
+        self.bodies.contains(handle.0)
+    }
+
+    pub fn body_count(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn collider_count(&self) -> usize {
+        self.colliders.len()
+    }
+
+    pub fn body_kind(&self, handle: BodyHandle) -> Option<BodyKind> {
+        let body = self.bodies.get(handle.0)?;
+        Some(match body.body_type() {
+            RigidBodyType::Dynamic => BodyKind::Dynamic,
+            RigidBodyType::KinematicPositionBased => BodyKind::Kinematic,
+            RigidBodyType::KinematicVelocityBased => BodyKind::KinematicVelocity,
+            RigidBodyType::Fixed => BodyKind::Fixed,
+        })
+    }
+
+    pub fn body_pose(&self, handle: BodyHandle) -> Option<Pose> {
+        self.bodies
+            .get(handle.0)
+            .map(|body| to_pose(body.position()))
+    }
+
+    pub fn set_body_pose(&mut self, handle: BodyHandle, pose: Pose) {
+        let Some(body) = self.bodies.get_mut(handle.0) else {
+            return;
+        };
+        let position = to_isometry(pose.position, pose.rotation);
+        body.set_position(position, true);
+        // Colliders normally follow their body during step; move them now so
+        // queries before the next step see the new pose.
+        for &collider in body.colliders() {
+            if let Some(collider) = self.colliders.get_mut(collider) {
+                let offset = collider.position_wrt_parent().copied().unwrap_or_default();
+                collider.set_position(position * offset);
+            }
+        }
+        self.update_queries();
+    }
+
+    pub fn set_kinematic_target(&mut self, handle: BodyHandle, pose: Pose) {
+        if let Some(body) = self.bodies.get_mut(handle.0) {
+            body.set_next_kinematic_position(to_isometry(pose.position, pose.rotation));
+        }
+    }
+
+    pub fn linear_velocity(&self, handle: BodyHandle) -> Option<Vec3> {
+        self.bodies
+            .get(handle.0)
+            .map(|body| from_vector(body.linvel()))
+    }
+
+    pub fn set_linear_velocity(&mut self, handle: BodyHandle, velocity: Vec3) {
+        if let Some(body) = self.bodies.get_mut(handle.0) {
+            body.set_linvel(to_vector(velocity), true);
+        }
+    }
+
+    pub fn angular_velocity(&self, handle: BodyHandle) -> Option<Vec3> {
+        self.bodies
+            .get(handle.0)
+            .map(|body| from_vector(body.angvel()))
+    }
+
+    pub fn set_angular_velocity(&mut self, handle: BodyHandle, velocity: Vec3) {
+        if let Some(body) = self.bodies.get_mut(handle.0) {
+            body.set_angvel(to_vector(velocity), true);
+        }
+    }
+
+    pub fn apply_impulse(&mut self, handle: BodyHandle, impulse: Vec3) {
+        if let Some(body) = self.bodies.get_mut(handle.0) {
+            body.apply_impulse(to_vector(impulse), true);
+        }
+    }
+
+    pub fn is_sleeping(&self, handle: BodyHandle) -> bool {
+        self.bodies
+            .get(handle.0)
+            .is_some_and(|body| body.is_sleeping())
+    }
+
+    pub fn is_ccd_enabled(&self, handle: BodyHandle) -> bool {
+        self.bodies
+            .get(handle.0)
+            .is_some_and(|body| body.is_ccd_enabled())
     }
 }
@@ -0,0 +1,255 @@
+use crate::error::EngineError;
+use crate::physics::body::build_shape;
+use crate::physics::convert::{from_vector, to_isometry, to_point, to_vector};
+use crate::physics::{
+    BodyHandle, ColliderHandle, ColliderShape, CollisionGroups, PhysicsWorld, Pose,
+};
+use glam::Vec3;
+use rapier3d::parry::query::TOIStatus;
+use rapier3d::prelude as rapier;
+
+#[derive(Debug, Clone, Default)]
+pub struct QueryFilter {
+    pub groups: Option<CollisionGroups>,
+    pub exclude_bodies: Vec<BodyHandle>,
+    pub exclude_colliders: Vec<ColliderHandle>,
+    pub exclude_sensors: bool,
+    pub exclude_fixed: bool,
+    pub exclude_kinematic: bool,
+    pub exclude_dynamic: bool,
+}
+
+impl QueryFilter {
+    pub fn excluding_body(body: BodyHandle) -> Self {
+        Self {
+            exclude_bodies: vec![body],
+            ..Self::default()
+        }
+    }
+
+    fn accepts(&self, handle: rapier::ColliderHandle, collider: &rapier::Collider) -> bool {
+        let excluded_body = collider
+            .parent()
+            .is_some_and(|parent| self.exclude_bodies.contains(&BodyHandle(parent)));
+        !excluded_body && !self.exclude_colliders.contains(&ColliderHandle(handle))
+    }
+
+    fn to_rapier<'a>(
+        &self,
+        predicate: &'a dyn Fn(rapier::ColliderHandle, &rapier::Collider) -> bool,
+    ) -> rapier::QueryFilter<'a> {
+        let mut flags = rapier::QueryFilterFlags::empty();
+        flags.set(
+            rapier::QueryFilterFlags::EXCLUDE_SENSORS,
+            self.exclude_sensors,
+        );
+        flags.set(rapier::QueryFilterFlags::EXCLUDE_FIXED, self.exclude_fixed);
+        flags.set(
+            rapier::QueryFilterFlags::EXCLUDE_KINEMATIC,
+            self.exclude_kinematic,
+        );
+        flags.set(
+            rapier::QueryFilterFlags::EXCLUDE_DYNAMIC,
+            self.exclude_dynamic,
+        );
+        rapier::QueryFilter {
+            flags,
+            groups: self.groups.map(CollisionGroups::to_interaction_groups),
+            exclude_collider: None,
+            exclude_rigid_body: None,
+            predicate: Some(predicate),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub collider: ColliderHandle,
+    pub body: Option<BodyHandle>,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeHit {
+    pub collider: ColliderHandle,
+    pub body: Option<BodyHandle>,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub distance: f32,
+    pub penetrating: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointHit {
+    pub collider: ColliderHandle,
+    pub body: Option<BodyHandle>,
+    pub point: Vec3,
+    pub inside: bool,
+}
+
+impl PhysicsWorld {
+    pub fn update_queries(&mut self) {
+        self.query_pipeline.update(&self.bodies, &self.colliders);
+    }
+
+    pub fn raycast(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &QueryFilter,
+    ) -> Option<RayHit> {
+        let ray = ray(origin, direction)?;
+        let predicate = |handle, collider: &rapier::Collider| filter.accepts(handle, collider);
+        let (handle, hit) = self.query_pipeline.cast_ray_and_get_normal(
+            &self.bodies,
+            &self.colliders,
+            &ray,
+            max_distance,
+            true,
+            filter.to_rapier(&predicate),
+        )?;
+        Some(self.ray_hit(&ray, handle, hit))
+    }
+
+    pub fn raycast_all(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &QueryFilter,
+    ) -> Vec<RayHit> {
+        let Some(ray) = ray(origin, direction) else {
+            return Vec::new();
+        };
+        let predicate = |handle, collider: &rapier::Collider| filter.accepts(handle, collider);
+        let mut hits = Vec::new();
+        self.query_pipeline.intersections_with_ray(
+            &self.bodies,
+            &self.colliders,
+            &ray,
+            max_distance,
+            true,
+            filter.to_rapier(&predicate),
+            |handle, hit| {
+                hits.push(self.ray_hit(&ray, handle, hit));
+                true
+            },
+        );
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    pub fn shape_cast(
+        &self,
+        shape: &ColliderShape,
+        pose: Pose,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &QueryFilter,
+    ) -> Result<Option<ShapeHit>, EngineError> {
+        let shape = build_shape(shape)?;
+        let Some(direction) = direction.try_normalize() else {
+            return Ok(None);
+        };
+        let predicate = |handle, collider: &rapier::Collider| filter.accepts(handle, collider);
+        let hit = self.query_pipeline.cast_shape(
+            &self.bodies,
+            &self.colliders,
+            &to_isometry(pose.position, pose.rotation),
+            &to_vector(direction),
+            shape.as_ref(),
+            max_distance,
+            true,
+            filter.to_rapier(&predicate),
+        );
+        Ok(hit.map(|(handle, toi)| ShapeHit {
+            collider: ColliderHandle(handle),
+            body: self.collider_body(ColliderHandle(handle)),
+            point: from_vector(&toi.witness1.coords),
+            normal: from_vector(&toi.normal1),
+            distance: toi.toi,
+            penetrating: toi.status == TOIStatus::Penetrating,
+        }))
+    }
+
+    pub fn project_point(&self, point: Vec3, filter: &QueryFilter) -> Option<PointHit> {
+        let predicate = |handle, collider: &rapier::Collider| filter.accepts(handle, collider);
+        let (handle, projection) = self.query_pipeline.project_point(
+            &self.bodies,
+            &self.colliders,
+            &to_point(point),
+            true,
+            filter.to_rapier(&predicate),
+        )?;
+        Some(PointHit {
+            collider: ColliderHandle(handle),
+            body: self.collider_body(ColliderHandle(handle)),
+            point: from_vector(&projection.point.coords),
+            inside: projection.is_inside,
+        })
+    }
+
+    pub fn overlap_aabb(&self, min: Vec3, max: Vec3, filter: &QueryFilter) -> Vec<ColliderHandle> {
+        let half_extents = ((max - min) * 0.5).abs();
+        let shape = rapier::SharedShape::cuboid(half_extents.x, half_extents.y, half_extents.z);
+        let pose = Pose {
+            position: (min + max) * 0.5,
+            ..Pose::default()
+        };
+        self.overlap(shape, pose, filter)
+    }
+
+    pub fn overlap_shape(
+        &self,
+        shape: &ColliderShape,
+        pose: Pose,
+        filter: &QueryFilter,
+    ) -> Result<Vec<ColliderHandle>, EngineError> {
+        Ok(self.overlap(build_shape(shape)?, pose, filter))
+    }
+
+    fn overlap(
+        &self,
+        shape: rapier::SharedShape,
+        pose: Pose,
+        filter: &QueryFilter,
+    ) -> Vec<ColliderHandle> {
+        let predicate = |handle, collider: &rapier::Collider| filter.accepts(handle, collider);
+        let mut colliders = Vec::new();
+        self.query_pipeline.intersections_with_shape(
+            &self.bodies,
+            &self.colliders,
+            &to_isometry(pose.position, pose.rotation),
+            shape.as_ref(),
+            filter.to_rapier(&predicate),
+            |handle| {
+                colliders.push(ColliderHandle(handle));
+                true
+            },
+        );
+        colliders
+    }
+
+    fn ray_hit(
+        &self,
+        ray: &rapier::Ray,
+        handle: rapier::ColliderHandle,
+        hit: rapier::RayIntersection,
+    ) -> RayHit {
+        RayHit {
+            collider: ColliderHandle(handle),
+            body: self.collider_body(ColliderHandle(handle)),
+            point: from_vector(&ray.point_at(hit.toi).coords),
+            normal: from_vector(&hit.normal),
+            distance: hit.toi,
+        }
+    }
+}
+
+fn ray(origin: Vec3, direction: Vec3) -> Option<rapier::Ray> {
+    let direction = direction.try_normalize()?;
+    Some(rapier::Ray::new(to_point(origin), to_vector(direction)))
+}
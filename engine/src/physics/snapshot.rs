@@ -0,0 +1,83 @@
+use crate::error::EngineError;
+use crate::physics::config::integration_parameters;
+use crate::physics::convert::to_vector;
+use crate::physics::{PhysicsConfig, PhysicsWorld};
+use rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct WorldState {
+    // Gravity and the integration parameters are derived from this on restore.
+    config: PhysicsConfig,
+    islands: IslandManager,
+    broad_phase: BroadPhase,
+    narrow_phase: NarrowPhase,
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    impulse_joints: ImpulseJointSet,
+    multibody_joints: MultibodyJointSet,
+    query_pipeline: QueryPipeline,
+}
+
+#[derive(Clone)]
+pub struct PhysicsSnapshot {
+    state: Box<WorldState>,
+}
+
+impl PhysicsSnapshot {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
+        bincode::serialize(&*self.state)
+            .map_err(|err| EngineError::Physics(format!("failed to encode snapshot: {err}")))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EngineError> {
+        let state = bincode::deserialize(bytes)
+            .map_err(|err| EngineError::Physics(format!("failed to decode snapshot: {err}")))?;
+        Ok(Self {
+            state: Box::new(state),
+        })
+    }
+
+    pub fn body_count(&self) -> usize {
+        self.state.bodies.len()
+    }
+
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.state.config
+    }
+}
+
+impl PhysicsWorld {
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            state: Box::new(WorldState {
+                config: self.config,
+                islands: self.islands.clone(),
+                broad_phase: self.broad_phase.clone(),
+                narrow_phase: self.narrow_phase.clone(),
+                bodies: self.bodies.clone(),
+                colliders: self.colliders.clone(),
+                impulse_joints: self.impulse_joints.clone(),
+                multibody_joints: self.multibody_joints.clone(),
+                query_pipeline: self.query_pipeline.clone(),
+            }),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &PhysicsSnapshot) {
+        let state = (*snapshot.state).clone();
+        self.config = state.config;
+        self.gravity = to_vector(state.config.gravity);
+        self.integration_parameters = integration_parameters(&state.config);
+        self.islands = state.islands;
+        self.broad_phase = state.broad_phase;
+        self.narrow_phase = state.narrow_phase;
+        self.bodies = state.bodies;
+        self.colliders = state.colliders;
+        self.impulse_joints = state.impulse_joints;
+        self.multibody_joints = state.multibody_joints;
+        self.query_pipeline = state.query_pipeline;
+        self.ccd_solver = CCDSolver::new();
+        self.events.clear();
+    }
+}
@@ -0,0 +1,60 @@
+use crate::physics::{BodyHandle, BodyKind, PhysicsWorld, Pose};
+use crate::scene::{Entity, Registry, Transform};
+use glam::Mat4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RigidBody(pub BodyHandle);
+
+impl RigidBody {
+    pub fn handle(&self) -> BodyHandle {
+        self.0
+    }
+}
+
+pub fn push_kinematic_targets(physics: &mut PhysicsWorld, registry: &Registry) {
+    for (entity, body) in linked_bodies(registry) {
+        if physics.body_kind(body) != Some(BodyKind::Kinematic) {
+            continue;
+        }
+        let world = Transform::from_matrix(registry.world_matrix(entity));
+        physics.set_kinematic_target(body, Pose::from_transform(&world));
+    }
+}
+
+pub fn pull_body_poses(physics: &PhysicsWorld, registry: &mut Registry) {
+    for (entity, body) in linked_bodies(registry) {
+        if !matches!(
+            physics.body_kind(body),
+            Some(BodyKind::Dynamic | BodyKind::KinematicVelocity)
+        ) {
+            continue;
+        }
+        let Some(pose) = physics.body_pose(body) else {
+            continue;
+        };
+        let local = match registry.parent(entity) {
+            Some(parent) => {
+                let scale = Transform::from_matrix(registry.world_matrix(entity)).scale;
+                let world =
+                    Mat4::from_scale_rotation_translation(scale, pose.rotation, pose.position);
+                Transform::from_matrix(registry.world_matrix(parent).inverse() * world)
+            }
+            None => {
+                let mut local = registry
+                    .get::<Transform>(entity)
+                    .copied()
+                    .unwrap_or_default();
+                pose.apply_to(&mut local);
+                local
+            }
+        };
+        registry.insert(entity, local);
+    }
+}
+
+fn linked_bodies(registry: &Registry) -> Vec<(Entity, BodyHandle)> {
+    registry
+        .iter::<RigidBody>()
+        .map(|(entity, body)| (entity, body.handle()))
+        .collect()
+}
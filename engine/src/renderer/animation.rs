@@ -0,0 +1,434 @@
+use crate::error::EngineError;
+use crate::renderer::Image;
+use color_quant::NeuQuant;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+// GIF frames held back to learn the palette from; later frames are mapped onto
+// it as they arrive.
+const PALETTE_FRAMES: usize = 8;
+
+// Pixels fed to the palette quantizer; larger frames are subsampled to keep
+// encoding time bounded.
+const PALETTE_SAMPLE_PIXELS: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "gif" => Some(Self::Gif),
+            "png" | "apng" => Some(Self::Apng),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    #[default]
+    Forever,
+    // Total number of plays, so Times(1) plays the animation once.
+    Times(u16),
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationConfig {
+    pub format: AnimationFormat,
+    pub frame_delay: Duration,
+    pub loop_mode: LoopMode,
+    // GIF only; frames share one global palette of this many colors (2..=256).
+    pub palette_size: u16,
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            format: AnimationFormat::Gif,
+            frame_delay: Duration::from_millis(100),
+            loop_mode: LoopMode::Forever,
+            palette_size: 256,
+        }
+    }
+}
+
+impl AnimationConfig {
+    pub fn at_fps(format: AnimationFormat, fps: u32) -> Self {
+        Self {
+            format,
+            frame_delay: Duration::from_secs_f64(1.0 / fps.max(1) as f64),
+            ..Self::default()
+        }
+    }
+}
+
+// Encodes equally sized frames as an animated GIF or APNG, writing each frame
+// out as it is pushed rather than holding the whole recording in memory.
+pub struct AnimationEncoder<W: Write + Seek> {
+    config: AnimationConfig,
+    size: Option<(u32, u32)>,
+    frames: usize,
+    // None once a write has failed; the output is unusable from then on.
+    sink: Option<Sink<W>>,
+}
+
+enum Sink<W: Write + Seek> {
+    // Nothing written yet; GIF frames wait here until the palette is learned.
+    Pending(W, Vec<Image>),
+    Gif(Box<GifSink<W>>),
+    Apng(ApngSink<W>),
+}
+
+impl AnimationEncoder<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>, config: AnimationConfig) -> Result<Self, EngineError> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .map_err(|err| EngineError::Io(format!("{}: {err}", path.display())))?;
+        Ok(Self::new(BufWriter::new(file), config))
+    }
+}
+
+impl<W: Write + Seek> AnimationEncoder<W> {
+    pub fn new(writer: W, config: AnimationConfig) -> Self {
+        Self {
+            config,
+            size: None,
+            frames: 0,
+            sink: Some(Sink::Pending(writer, Vec::new())),
+        }
+    }
+
+    pub fn config(&self) -> &AnimationConfig {
+        &self.config
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    pub fn push_frame(&mut self, frame: Image) -> Result<(), EngineError> {
+        let size = (frame.width(), frame.height());
+        match self.size {
+            Some(expected) if expected != size => {
+                return Err(EngineError::Image(format!(
+                    "animation frame is {}x{}, expected {}x{}",
+                    size.0, size.1, expected.0, expected.1
+                )));
+            }
+            Some(_) => {}
+            None => {
+                check_size(self.config.format, size)?;
+                self.size = Some(size);
+            }
+        }
+        let sink = match self.sink.take().ok_or_else(failed)? {
+            Sink::Pending(writer, mut frames) if self.config.format == AnimationFormat::Gif => {
+                frames.push(frame);
+                if frames.len() < PALETTE_FRAMES {
+                    Sink::Pending(writer, frames)
+                } else {
+                    Sink::Gif(Box::new(GifSink::start(writer, &self.config, &frames)?))
+                }
+            }
+            Sink::Pending(writer, _) => {
+                let mut apng = ApngSink::start(writer, &self.config, size)?;
+                apng.write(&frame)?;
+                Sink::Apng(apng)
+            }
+            Sink::Gif(mut gif) => {
+                gif.write(&frame)?;
+                Sink::Gif(gif)
+            }
+            Sink::Apng(mut apng) => {
+                apng.write(&frame)?;
+                Sink::Apng(apng)
+            }
+        };
+        self.sink = Some(sink);
+        self.frames += 1;
+        Ok(())
+    }
+
+    // Writes the trailer and returns the flushed writer.
+    pub fn finish(self) -> Result<W, EngineError> {
+        let mut writer = match self.sink.ok_or_else(failed)? {
+            Sink::Pending(_, frames) if frames.is_empty() => {
+                return Err(EngineError::Image("animation has no frames".to_string()));
+            }
+            Sink::Pending(writer, frames) => {
+                GifSink::start(writer, &self.config, &frames)?.finish()?
+            }
+            Sink::Gif(gif) => gif.finish()?,
+            Sink::Apng(apng) => apng.finish()?,
+        };
+        writer.flush().map_err(io_error)?;
+        Ok(writer)
+    }
+}
+
+fn check_size(format: AnimationFormat, (width, height): (u32, u32)) -> Result<(), EngineError> {
+    if width == 0 || height == 0 {
+        return Err(EngineError::Image("animation frames are empty".to_string()));
+    }
+    if format == AnimationFormat::Gif && (width > u16::MAX as u32 || height > u16::MAX as u32) {
+        return Err(EngineError::Image(format!(
+            "gif frames are limited to 65535x65535, got {width}x{height}"
+        )));
+    }
+    Ok(())
+}
+
+fn failed() -> EngineError {
+    EngineError::Image("animation encoder failed on an earlier frame".to_string())
+}
+
+fn io_error(err: std::io::Error) -> EngineError {
+    EngineError::Io(format!("animation write: {err}"))
+}
+
+fn gif_error(err: gif::EncodingError) -> EngineError {
+    EngineError::Image(format!("gif encode: {err}"))
+}
+
+fn png_error(err: png::EncodingError) -> EngineError {
+    EngineError::Image(format!("apng encode: {err}"))
+}
+
+struct GifSink<W: Write> {
+    encoder: gif::Encoder<W>,
+    palette: Palette,
+    indices: HashMap<[u8; 3], u8>,
+    frame_delay: Duration,
+    written: usize,
+}
+
+impl<W: Write> GifSink<W> {
+    // Writes the header with a palette learned from `frames`, then the frames.
+    fn start(writer: W, config: &AnimationConfig, frames: &[Image]) -> Result<Self, EngineError> {
+        // check_size already limited these to u16.
+        let (width, height) = (frames[0].width() as u16, frames[0].height() as u16);
+        let palette = Palette::build(frames, config.palette_size);
+        let mut encoder =
+            gif::Encoder::new(writer, width, height, &palette.colors).map_err(gif_error)?;
+        let repeat = match config.loop_mode {
+            LoopMode::Forever => gif::Repeat::Infinite,
+            LoopMode::Times(plays) => gif::Repeat::Finite(plays.saturating_sub(1)),
+        };
+        encoder.set_repeat(repeat).map_err(gif_error)?;
+        let mut sink = Self {
+            encoder,
+            palette,
+            indices: HashMap::new(),
+            frame_delay: config.frame_delay,
+            written: 0,
+        };
+        for frame in frames {
+            sink.write(frame)?;
+        }
+        Ok(sink)
+    }
+
+    fn write(&mut self, frame: &Image) -> Result<(), EngineError> {
+        let buffer: Vec<u8> = frame
+            .pixels()
+            .chunks_exact(4)
+            .map(|rgba| {
+                self.palette
+                    .index_of([rgba[0], rgba[1], rgba[2]], &mut self.indices)
+            })
+            .collect();
+        let frame = gif::Frame {
+            delay: frame_delay(self.written, self.frame_delay, 100),
+            width: frame.width() as u16,
+            height: frame.height() as u16,
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        };
+        self.encoder.write_frame(&frame).map_err(gif_error)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<W, EngineError> {
+        self.encoder.into_inner().map_err(io_error)
+    }
+}
+
+// APNG chunks are written by hand because the png crate needs the frame count
+// up front; acTL gets a placeholder count that finish patches in place.
+struct ApngSink<W: Write + Seek> {
+    writer: W,
+    control_offset: u64,
+    plays: u32,
+    frame_delay: Duration,
+    written: u32,
+    sequence: u32,
+}
+
+impl<W: Write + Seek> ApngSink<W> {
+    fn start(
+        mut writer: W,
+        config: &AnimationConfig,
+        (width, height): (u32, u32),
+    ) -> Result<Self, EngineError> {
+        let plays = match config.loop_mode {
+            LoopMode::Forever => 0,
+            LoopMode::Times(plays) => u32::from(plays.max(1)),
+        };
+        writer.write_all(b"\x89PNG\r\n\x1a\n").map_err(io_error)?;
+        let mut header = [0; 13];
+        header[..4].copy_from_slice(&width.to_be_bytes());
+        header[4..8].copy_from_slice(&height.to_be_bytes());
+        // 8-bit RGBA, default compression and filtering, no interlacing.
+        header[8..].copy_from_slice(&[8, 6, 0, 0, 0]);
+        write_chunk(&mut writer, b"IHDR", &header)?;
+        let control_offset = writer.stream_position().map_err(io_error)?;
+        write_chunk(&mut writer, b"acTL", &animation_control(0, plays))?;
+        Ok(Self {
+            writer,
+            control_offset,
+            plays,
+            frame_delay: config.frame_delay,
+            written: 0,
+            sequence: 0,
+        })
+    }
+
+    fn write(&mut self, frame: &Image) -> Result<(), EngineError> {
+        let delay = frame_delay(self.written as usize, self.frame_delay, 1000);
+        let mut control = Vec::with_capacity(26);
+        control.extend_from_slice(&self.next_sequence().to_be_bytes());
+        control.extend_from_slice(&frame.width().to_be_bytes());
+        control.extend_from_slice(&frame.height().to_be_bytes());
+        control.extend_from_slice(&[0; 8]);
+        control.extend_from_slice(&delay.to_be_bytes());
+        control.extend_from_slice(&1000u16.to_be_bytes());
+        // Every frame covers the canvas: no disposal, replace rather than blend.
+        control.extend_from_slice(&[0, 0]);
+        write_chunk(&mut self.writer, b"fcTL", &control)?;
+
+        // Let the png crate filter and compress the frame, then copy its image
+        // data out: as IDAT for the first frame, as numbered fdAT after that.
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, frame.width(), frame.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut image = encoder.write_header().map_err(png_error)?;
+        image.write_image_data(frame.pixels()).map_err(png_error)?;
+        image.finish().map_err(png_error)?;
+        for data in image_data(&png) {
+            if self.written == 0 {
+                write_chunk(&mut self.writer, b"IDAT", data)?;
+            } else {
+                let mut chunk = Vec::with_capacity(data.len() + 4);
+                chunk.extend_from_slice(&self.next_sequence().to_be_bytes());
+                chunk.extend_from_slice(data);
+                write_chunk(&mut self.writer, b"fdAT", &chunk)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<W, EngineError> {
+        write_chunk(&mut self.writer, b"IEND", &[])?;
+        let control = animation_control(self.written, self.plays);
+        self.writer
+            .seek(SeekFrom::Start(self.control_offset))
+            .map_err(io_error)?;
+        write_chunk(&mut self.writer, b"acTL", &control)?;
+        self.writer.seek(SeekFrom::End(0)).map_err(io_error)?;
+        Ok(self.writer)
+    }
+
+    fn next_sequence(&mut self) -> u32 {
+        self.sequence += 1;
+        self.sequence - 1
+    }
+}
+
+fn animation_control(frames: u32, plays: u32) -> [u8; 8] {
+    let mut control = [0; 8];
+    control[..4].copy_from_slice(&frames.to_be_bytes());
+    control[4..].copy_from_slice(&plays.to_be_bytes());
+    control
+}
+
+fn write_chunk(writer: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> Result<(), EngineError> {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+    let length = data.len() as u32;
+    writer
+        .write_all(&length.to_be_bytes())
+        .and_then(|()| writer.write_all(kind))
+        .and_then(|()| writer.write_all(data))
+        .and_then(|()| writer.write_all(&crc.finalize().to_be_bytes()))
+        .map_err(io_error)
+}
+
+// The IDAT payloads of a PNG the png crate just wrote.
+fn image_data(png: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = &png[8..];
+    std::iter::from_fn(move || loop {
+        if rest.len() < 12 {
+            return None;
+        }
+        let length = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let (chunk, next) = rest.split_at(12 + length);
+        rest = next;
+        if &chunk[4..8] == b"IDAT" {
+            return Some(&chunk[8..8 + length]);
+        }
+    })
+}
+
+// Delay of frame `index` in 1/units seconds, rounded against the running total
+// so delays that don't divide evenly (e.g. 30 fps in centiseconds) don't drift.
+fn frame_delay(index: usize, delay: Duration, units: u32) -> u16 {
+    let step = delay.as_secs_f64() * units as f64;
+    let start = (index as f64 * step).round();
+    let end = ((index + 1) as f64 * step).round();
+    (end - start).clamp(0.0, u16::MAX as f64) as u16
+}
+
+struct Palette {
+    quantizer: NeuQuant,
+    colors: Vec<u8>,
+}
+
+impl Palette {
+    // One palette for the whole animation, so colors stay stable frame to frame.
+    fn build(frames: &[Image], size: u16) -> Self {
+        let total: usize = frames.iter().map(|frame| frame.pixels().len() / 4).sum();
+        let stride = total.div_ceil(PALETTE_SAMPLE_PIXELS).max(1);
+        let mut samples = Vec::with_capacity(total / stride * 4 + 4);
+        for frame in frames {
+            for rgba in frame.pixels().chunks_exact(4).step_by(stride) {
+                samples.extend_from_slice(&[rgba[0], rgba[1], rgba[2], 255]);
+            }
+        }
+        let quantizer = NeuQuant::new(10, usize::from(size.clamp(2, 256)), &samples);
+        let colors = quantizer.color_map_rgb();
+        Self { quantizer, colors }
+    }
+
+    fn index_of(&self, rgb: [u8; 3], cache: &mut HashMap<[u8; 3], u8>) -> u8 {
+        *cache
+            .entry(rgb)
+            .or_insert_with(|| self.quantizer.index_of(&[rgb[0], rgb[1], rgb[2], 255]) as u8)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::error::EngineError;
+use crate::renderer::{Image, Mesh, MeshHandle, RenderFrame, TextureHandle};
+use std::fmt;
+use std::sync::Arc;
+use winit::window::Window;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackendKind {
+    Dx11,
+    Software,
+    Null,
+}
+
+impl BackendKind {
+    pub fn default_fallback_order() -> Vec<BackendKind> {
+        vec![BackendKind::Dx11, BackendKind::Software]
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BackendKind::Dx11 => "dx11",
+            BackendKind::Software => "software",
+            BackendKind::Null => "null",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    pub presents_to_window: bool,
+    pub offscreen: bool,
+    pub hardware_accelerated: bool,
+}
+
+#[derive(Clone)]
+pub enum RenderTarget {
+    Window(Arc<Window>),
+    Offscreen { width: u32, height: u32 },
+}
+
+pub trait RenderBackend {
+    fn new(target: RenderTarget) -> Result<Self, EngineError>
+    where
+        Self: Sized;
+
+    fn kind(&self) -> BackendKind;
+
+    fn capabilities(&self) -> BackendCapabilities;
+
+    fn resize(&mut self, width: u32, height: u32);
+
+    fn upload_mesh(&mut self, handle: MeshHandle, mesh: &Mesh) -> Result<(), EngineError>;
+
+    fn release_mesh(&mut self, handle: MeshHandle);
+
+    fn upload_texture(&mut self, handle: TextureHandle, image: &Image) -> Result<(), EngineError>;
+
+    fn release_texture(&mut self, handle: TextureHandle);
+
+    fn render(&mut self, frame: &RenderFrame) -> Result<(), EngineError>;
+
+    fn capture(&mut self) -> Result<Image, EngineError>;
+}
@@ -0,0 +1,71 @@
+use crate::renderer::{Material, MaterialHandle, MeshHandle, RenderFrame};
+use crate::scene::Scene;
+use glam::Mat4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshRenderer {
+    pub mesh: MeshHandle,
+    pub material: Option<MaterialHandle>,
+    pub visible: bool,
+}
+
+impl MeshRenderer {
+    pub fn new(mesh: MeshHandle) -> Self {
+        Self {
+            mesh,
+            material: None,
+            visible: true,
+        }
+    }
+
+    pub fn with_material(mut self, material: MaterialHandle) -> Self {
+        self.material = Some(material);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawItem {
+    pub mesh: MeshHandle,
+    pub material: Material,
+    pub world: Mat4,
+}
+
+impl DrawItem {
+    pub fn new(mesh: MeshHandle, world: Mat4) -> Self {
+        Self {
+            mesh,
+            material: Material::default(),
+            world,
+        }
+    }
+}
+
+pub fn collect_draw_items(scene: &Scene) -> Vec<DrawItem> {
+    scene
+        .registry
+        .iter::<MeshRenderer>()
+        .filter(|(_, renderer)| renderer.visible)
+        .map(|(entity, renderer)| DrawItem {
+            mesh: renderer.mesh,
+            material: renderer
+                .material
+                .and_then(|material| scene.materials.get(material))
+                .copied()
+                .unwrap_or_default(),
+            world: scene.registry.world_matrix(entity),
+        })
+        .collect()
+}
+
+// Opaque and masked items keep their order; blended items follow, back to front.
+pub(crate) fn sorted_draw_items(frame: &RenderFrame) -> Vec<&DrawItem> {
+    let (mut items, mut blended): (Vec<&DrawItem>, Vec<&DrawItem>) = frame
+        .draw_items
+        .iter()
+        .partition(|item| !item.material.is_blended());
+    let depth = |item: &DrawItem| (frame.view_projection * item.world.w_axis).w;
+    blended.sort_by(|a, b| depth(b).total_cmp(&depth(a)));
+    items.extend(blended);
+    items
+}
@@ -1,12 +1,21 @@
-#[cfg(target_os = "windows")]
 use crate::error::EngineError;
+use crate::renderer::backend::{RenderBackend, RenderTarget};
+#[cfg(target_os = "windows")]
+use crate::renderer::backend::{BackendCapabilities, BackendKind};
 #[cfg(target_os = "windows")]
-use crate::renderer::RenderFrame;
+use crate::debug_draw::DebugLine;
 #[cfg(target_os = "windows")]
-use glam::{Mat4, Vec3, Vec4};
+use crate::renderer::{
+    sorted_draw_items, AlphaMode, FilterMode, Image, Material, Mesh, MeshHandle, RenderFrame,
+    Sampler, SpriteSpace, Submesh, TextureHandle, WrapMode,
+};
+#[cfg(target_os = "windows")]
+use glam::{Mat4, Vec4};
 #[cfg(target_os = "windows")]
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 #[cfg(target_os = "windows")]
+use std::collections::HashMap;
+#[cfg(target_os = "windows")]
 use std::ffi::CString;
 #[cfg(target_os = "windows")]
 use std::mem::size_of;
@@ -14,7 +23,8 @@ use std::mem::size_of;
 use windows::Win32::Foundation::HWND;
 #[cfg(target_os = "windows")]
 use windows::Win32::Graphics::Direct3D::{
-    D3D_DRIVER_TYPE_HARDWARE, D3D_FEATURE_LEVEL_11_0, D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST,
+    D3D_DRIVER_TYPE_HARDWARE, D3D_FEATURE_LEVEL_11_0, D3D_PRIMITIVE_TOPOLOGY_LINELIST,
+    D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST,
 };
 #[cfg(target_os = "windows")]
 use windows::Win32::Graphics::Direct3D::Fxc::{
@@ -22,13 +32,24 @@ use windows::Win32::Graphics::Direct3D::Fxc::{
 };
 #[cfg(target_os = "windows")]
 use windows::Win32::Graphics::Direct3D11::{
-    D3D11CreateDeviceAndSwapChain, D3D11_BUFFER_DESC, D3D11_INPUT_ELEMENT_DESC,
-    D3D11_SUBRESOURCE_DATA, D3D11_TEXTURE2D_DESC, ID3D11Buffer, ID3D11DepthStencilView,
-    ID3D11Device, ID3D11DeviceContext, ID3D11InputLayout, ID3D11PixelShader,
-    ID3D11RenderTargetView, ID3D11Texture2D, ID3D11VertexShader, D3D11_BIND_CONSTANT_BUFFER,
-    D3D11_BIND_DEPTH_STENCIL, D3D11_BIND_INDEX_BUFFER, D3D11_BIND_VERTEX_BUFFER,
-    D3D11_CLEAR_DEPTH, D3D11_CLEAR_STENCIL, D3D11_CREATE_DEVICE_BGRA_SUPPORT,
-    D3D11_INPUT_PER_VERTEX_DATA, D3D11_SDK_VERSION, D3D11_USAGE_DEFAULT,
+    D3D11CreateDevice, D3D11CreateDeviceAndSwapChain, D3D11_BLEND_DESC, D3D11_BUFFER_DESC,
+    D3D11_DEPTH_STENCIL_DESC, D3D11_INPUT_ELEMENT_DESC, D3D11_MAPPED_SUBRESOURCE,
+    D3D11_RASTERIZER_DESC, D3D11_RENDER_TARGET_BLEND_DESC, D3D11_SAMPLER_DESC,
+    D3D11_SUBRESOURCE_DATA, D3D11_TEXTURE2D_DESC, ID3D11BlendState, ID3D11Buffer,
+    ID3D11DepthStencilState, ID3D11DepthStencilView, ID3D11Device, ID3D11DeviceContext,
+    ID3D11InputLayout, ID3D11PixelShader, ID3D11RasterizerState, ID3D11RenderTargetView,
+    ID3D11SamplerState, ID3D11ShaderResourceView, ID3D11Texture2D, ID3D11VertexShader,
+    D3D11_BIND_CONSTANT_BUFFER, D3D11_BIND_DEPTH_STENCIL, D3D11_BIND_INDEX_BUFFER,
+    D3D11_BIND_RENDER_TARGET, D3D11_BIND_SHADER_RESOURCE, D3D11_BIND_VERTEX_BUFFER,
+    D3D11_BLEND_INV_SRC_ALPHA, D3D11_BLEND_ONE, D3D11_BLEND_OP_ADD, D3D11_BLEND_SRC_ALPHA,
+    D3D11_BLEND_ZERO, D3D11_CLEAR_DEPTH, D3D11_CLEAR_STENCIL, D3D11_COLOR_WRITE_ENABLE_ALL,
+    D3D11_COMPARISON_LESS, D3D11_COMPARISON_NEVER, D3D11_CPU_ACCESS_READ,
+    D3D11_CPU_ACCESS_WRITE, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_CULL_BACK, D3D11_CULL_NONE,
+    D3D11_DEPTH_WRITE_MASK_ZERO, D3D11_FILL_SOLID, D3D11_FILTER_MIN_MAG_MIP_LINEAR,
+    D3D11_FILTER_MIN_MAG_MIP_POINT, D3D11_FLOAT32_MAX, D3D11_INPUT_PER_VERTEX_DATA,
+    D3D11_MAP_READ, D3D11_MAP_WRITE_DISCARD, D3D11_SDK_VERSION, D3D11_TEXTURE_ADDRESS_CLAMP,
+    D3D11_TEXTURE_ADDRESS_MIRROR, D3D11_TEXTURE_ADDRESS_WRAP, D3D11_USAGE_DEFAULT,
+    D3D11_USAGE_DYNAMIC, D3D11_USAGE_IMMUTABLE, D3D11_USAGE_STAGING,
 };
 #[cfg(target_os = "windows")]
 use windows::Win32::Graphics::Dxgi::{
@@ -36,8 +57,9 @@ use windows::Win32::Graphics::Dxgi::{
 };
 #[cfg(target_os = "windows")]
 use windows::Win32::Graphics::Dxgi::Common::{
-    DXGI_FORMAT_D24_UNORM_S8_UINT, DXGI_FORMAT_R16_UINT, DXGI_FORMAT_R32G32B32_FLOAT,
-    DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_MODE_DESC, DXGI_SAMPLE_DESC,
+    DXGI_FORMAT_D24_UNORM_S8_UINT, DXGI_FORMAT_R32G32B32A32_FLOAT, DXGI_FORMAT_R32G32B32_FLOAT,
+    DXGI_FORMAT_R32G32_FLOAT, DXGI_FORMAT_R32_UINT, DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_MODE_DESC,
+    DXGI_SAMPLE_DESC,
 };
 #[cfg(target_os = "windows")]
 use windows::core::PCSTR;
@@ -46,101 +68,101 @@ use windows::core::PCSTR;
 pub struct Dx11Renderer {
     device: ID3D11Device,
     context: ID3D11DeviceContext,
-    swap_chain: IDXGISwapChain,
+    swap_chain: Option<IDXGISwapChain>,
+    color_texture: ID3D11Texture2D,
     render_target: ID3D11RenderTargetView,
     depth_view: ID3D11DepthStencilView,
     vertex_shader: ID3D11VertexShader,
     pixel_shader: ID3D11PixelShader,
     input_layout: ID3D11InputLayout,
-    vertex_buffer: ID3D11Buffer,
-    index_buffer: ID3D11Buffer,
     constant_buffer: ID3D11Buffer,
+    meshes: HashMap<MeshHandle, GpuMesh>,
+    textures: HashMap<TextureHandle, ID3D11ShaderResourceView>,
+    white_texture: ID3D11ShaderResourceView,
+    samplers: HashMap<Sampler, ID3D11SamplerState>,
+    states: PipelineStates,
+    line_buffer: DynamicBuffer,
+    sprite_buffer: DynamicBuffer,
     width: u32,
     height: u32,
 }
 
 #[cfg(target_os = "windows")]
-impl Dx11Renderer {
-    pub fn new(window: &winit::window::Window) -> Result<Self, EngineError> {
-        let hwnd = window_handle(window)?;
-        let size = window.inner_size();
-        let width = size.width.max(1);
-        let height = size.height.max(1);
-
-        let swap_chain_desc = DXGI_SWAP_CHAIN_DESC {
-            BufferDesc: DXGI_MODE_DESC {
-                Width: width,
-                Height: height,
-                Format: DXGI_FORMAT_R8G8B8A8_UNORM,
-                ..Default::default()
-            },
-            SampleDesc: DXGI_SAMPLE_DESC {
-                Count: 1,
-                Quality: 0,
-            },
-            BufferUsage: windows::Win32::Graphics::Dxgi::DXGI_USAGE_RENDER_TARGET_OUTPUT,
-            BufferCount: 2,
-            OutputWindow: hwnd,
-            Windowed: true.into(),
-            SwapEffect: DXGI_SWAP_EFFECT_DISCARD,
-            ..Default::default()
-        };
-
-        let mut device = None;
-        let mut context = None;
-        let mut swap_chain = None;
+pub fn create_backend(target: RenderTarget) -> Result<Box<dyn RenderBackend>, EngineError> {
+    Ok(Box::new(Dx11Renderer::new(target)?))
+}
 
-        unsafe {
-            D3D11CreateDeviceAndSwapChain(
-                None,
-                D3D_DRIVER_TYPE_HARDWARE,
-                None,
-                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
-                Some(&[D3D_FEATURE_LEVEL_11_0]),
-                D3D11_SDK_VERSION,
-                Some(&swap_chain_desc),
-                Some(&mut swap_chain),
-                Some(&mut device),
-                None,
-                Some(&mut context),
-            )
-            .map_err(|err| EngineError::RendererInit(format!("D3D11 init failed: {err:?}")))?;
-        }
+#[cfg(not(target_os = "windows"))]
+pub fn create_backend(_target: RenderTarget) -> Result<Box<dyn RenderBackend>, EngineError> {
+    Err(EngineError::UnsupportedPlatform(
+        "DirectX 11 renderer requires Windows".to_string(),
+    ))
+}
 
-        let device = device.ok_or_else(|| {
-            EngineError::RendererInit("missing D3D11 device".to_string())
-        })?;
-        let context = context.ok_or_else(|| {
-            EngineError::RendererInit("missing D3D11 context".to_string())
-        })?;
-        let swap_chain = swap_chain.ok_or_else(|| {
-            EngineError::RendererInit("missing swap chain".to_string())
-        })?;
+#[cfg(target_os = "windows")]
+impl RenderBackend for Dx11Renderer {
+    fn new(target: RenderTarget) -> Result<Self, EngineError> {
+        let (device, context, swap_chain, width, height) = match target {
+            RenderTarget::Window(window) => {
+                let size = window.inner_size();
+                let width = size.width.max(1);
+                let height = size.height.max(1);
+                let (device, context, swap_chain) =
+                    create_device_and_swap_chain(&window, width, height)?;
+                (device, context, Some(swap_chain), width, height)
+            }
+            RenderTarget::Offscreen { width, height } => {
+                let (device, context) = create_device()?;
+                (device, context, None, width.max(1), height.max(1))
+            }
+        };
 
-        let render_target = create_render_target(&device, &swap_chain)?;
+        let (color_texture, render_target) = create_color_target(&device, width, height)?;
         let depth_view = create_depth_stencil_view(&device, width, height)?;
         set_viewport(&context, width, height);
         let shader_bundle = create_shaders(&device)?;
-        let buffers = create_cube_buffers(&device)?;
+        let constant_buffer = create_constant_buffer(&device)?;
+        let white_texture = create_texture(&device, &Image::from_rgba(1, 1, vec![255; 4])?)
+            .map_err(|err| EngineError::RendererInit(format!("white texture: {err}")))?;
+        let samplers = create_samplers(&device)?;
+        let states = create_pipeline_states(&device)?;
 
         Ok(Self {
             device,
             context,
             swap_chain,
+            color_texture,
             render_target,
             depth_view,
             vertex_shader: shader_bundle.vertex_shader,
             pixel_shader: shader_bundle.pixel_shader,
             input_layout: shader_bundle.input_layout,
-            vertex_buffer: buffers.vertex_buffer,
-            index_buffer: buffers.index_buffer,
-            constant_buffer: buffers.constant_buffer,
+            constant_buffer,
+            meshes: HashMap::new(),
+            textures: HashMap::new(),
+            white_texture,
+            samplers,
+            states,
+            line_buffer: DynamicBuffer::default(),
+            sprite_buffer: DynamicBuffer::default(),
             width,
             height,
         })
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Dx11
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            presents_to_window: self.swap_chain.is_some(),
+            offscreen: true,
+            hardware_accelerated: true,
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
         if width == 0 || height == 0 {
             return;
         }
@@ -152,11 +174,14 @@ impl Dx11Renderer {
         unsafe {
             self.context
                 .OMSetRenderTargets(None, None);
-            let _ = self
-                .swap_chain
-                .ResizeBuffers(0, width, height, DXGI_FORMAT_R8G8B8A8_UNORM, 0);
+            if let Some(swap_chain) = self.swap_chain.as_ref() {
+                let _ = swap_chain.ResizeBuffers(0, width, height, DXGI_FORMAT_R8G8B8A8_UNORM, 0);
+            }
         }
-        if let Ok(render_target) = create_render_target(&self.device, &self.swap_chain) {
+        if let Ok((color_texture, render_target)) =
+            create_color_target(&self.device, width, height)
+        {
+            self.color_texture = color_texture;
             self.render_target = render_target;
             set_viewport(&self.context, width, height);
         }
@@ -165,12 +190,8 @@ impl Dx11Renderer {
         }
     }
 
-    pub fn render(&mut self, frame: RenderFrame) -> Result<(), EngineError> {
+    fn render(&mut self, frame: &RenderFrame) -> Result<(), EngineError> {
         let color = vec4_to_color(frame.clear_color);
-        let transform = cube_transform(frame.time_seconds, self.width, self.height);
-        let constant_data = ConstantBuffer {
-            mvp: transform.to_cols_array_2d(),
-        };
         unsafe {
             self.context.OMSetRenderTargets(
                 Some(&[Some(self.render_target.clone())]),
@@ -184,119 +205,387 @@ impl Dx11Renderer {
                 0,
             );
             self.context.IASetInputLayout(Some(&self.input_layout));
-            let stride = size_of::<Vertex>() as u32;
-            let offset = 0u32;
-            let buffers = [Some(self.vertex_buffer.clone())];
-            let strides = [stride];
-            let offsets = [offset];
-            self.context.IASetVertexBuffers(
-                0,
-                1,
-                Some(buffers.as_ptr()),
-                Some(strides.as_ptr()),
-                Some(offsets.as_ptr()),
-            );
-            self.context.IASetIndexBuffer(&self.index_buffer, DXGI_FORMAT_R16_UINT, 0);
-            self.context
-                .IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
             self.context.VSSetShader(Some(&self.vertex_shader), None);
             self.context.PSSetShader(Some(&self.pixel_shader), None);
-            self.context.UpdateSubresource(
-                &self.constant_buffer,
-                0,
-                None,
-                &constant_data as *const ConstantBuffer as *const _,
-                0,
-                0,
-            );
             self.context
                 .VSSetConstantBuffers(0, Some(&[Some(self.constant_buffer.clone())]));
-            self.context.DrawIndexed(CUBE_INDEX_COUNT, 0, 0);
-            self.swap_chain
-                .Present(1, 0)
-                .ok()
-                .map_err(|err| EngineError::Runtime(format!("present failed: {err:?}")))?;
+            self.context
+                .PSSetConstantBuffers(0, Some(&[Some(self.constant_buffer.clone())]));
+            for item in sorted_draw_items(frame) {
+                let Some(mesh) = self.meshes.get(&item.mesh) else {
+                    continue;
+                };
+                self.bind_material(&item.material);
+                self.update_constants(&ConstantBuffer {
+                    mvp: (frame.view_projection * item.world).to_cols_array_2d(),
+                    tint: item.material.base_color.to_array(),
+                    params: material_params(&item.material),
+                });
+                self.draw_mesh(mesh);
+            }
+            self.draw_sprites(frame)?;
+            self.draw_lines(frame)?;
+            if let Some(swap_chain) = self.swap_chain.as_ref() {
+                let back_buffer: ID3D11Texture2D = swap_chain
+                    .GetBuffer(0)
+                    .map_err(|err| EngineError::Runtime(format!("back buffer: {err:?}")))?;
+                self.context.CopyResource(&back_buffer, &self.color_texture);
+                swap_chain
+                    .Present(1, 0)
+                    .ok()
+                    .map_err(|err| EngineError::Runtime(format!("present failed: {err:?}")))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn upload_mesh(&mut self, handle: MeshHandle, mesh: &Mesh) -> Result<(), EngineError> {
+        let gpu_mesh = create_gpu_mesh(&self.device, mesh)?;
+        self.meshes.insert(handle, gpu_mesh);
+        Ok(())
+    }
+
+    fn release_mesh(&mut self, handle: MeshHandle) {
+        self.meshes.remove(&handle);
+    }
+
+    fn upload_texture(&mut self, handle: TextureHandle, image: &Image) -> Result<(), EngineError> {
+        let view = create_texture(&self.device, image)?;
+        self.textures.insert(handle, view);
+        Ok(())
+    }
+
+    fn release_texture(&mut self, handle: TextureHandle) {
+        self.textures.remove(&handle);
+    }
+
+    fn capture(&mut self) -> Result<Image, EngineError> {
+        let desc = D3D11_TEXTURE2D_DESC {
+            Width: self.width,
+            Height: self.height,
+            MipLevels: 1,
+            ArraySize: 1,
+            Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Usage: D3D11_USAGE_STAGING,
+            CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+            ..Default::default()
+        };
+        let row_bytes = (self.width * 4) as usize;
+        let mut pixels = vec![0u8; row_bytes * self.height as usize];
+        unsafe {
+            let mut staging = None;
+            self.device
+                .CreateTexture2D(&desc, None, Some(&mut staging))
+                .map_err(|err| EngineError::Runtime(format!("staging texture: {err:?}")))?;
+            let staging = staging.ok_or_else(|| {
+                EngineError::Runtime("missing staging texture".to_string())
+            })?;
+            self.context.CopyResource(&staging, &self.color_texture);
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context
+                .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                .map_err(|err| EngineError::Runtime(format!("map staging: {err:?}")))?;
+            for (row, target) in pixels.chunks_exact_mut(row_bytes).enumerate() {
+                let source = std::slice::from_raw_parts(
+                    (mapped.pData as *const u8).add(row * mapped.RowPitch as usize),
+                    row_bytes,
+                );
+                target.copy_from_slice(source);
+            }
+            self.context.Unmap(&staging, 0);
+        }
+        Image::from_rgba(self.width, self.height, pixels)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Dx11Renderer {
+    unsafe fn draw_lines(&mut self, frame: &RenderFrame) -> Result<(), EngineError> {
+        if frame.debug_lines.is_empty() {
+            return Ok(());
         }
+        let vertices = line_vertices(&frame.debug_lines);
+        let line_buffer = self
+            .line_buffer
+            .upload(&self.device, &self.context, &vertices)?;
+
+        self.bind_material(&Material {
+            alpha_mode: AlphaMode::Blend,
+            two_sided: true,
+            ..Material::default()
+        });
+        self.update_constants(&ConstantBuffer {
+            mvp: frame.view_projection.to_cols_array_2d(),
+            tint: [1.0; 4],
+            params: [-1.0, 0.0, 0.0, 0.0],
+        });
+        // Debug lines are an overlay, so they skip the depth test entirely.
+        self.context
+            .OMSetRenderTargets(Some(&[Some(self.render_target.clone())]), None);
+        self.bind_vertex_buffer(line_buffer);
+        self.context
+            .IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_LINELIST);
+        self.context.Draw(vertices.len() as u32, 0);
         Ok(())
     }
+
+    unsafe fn draw_sprites(&mut self, frame: &RenderFrame) -> Result<(), EngineError> {
+        if frame.sprites.is_empty() {
+            return Ok(());
+        }
+        // All batches share one dynamic buffer; each batch is a single Draw call.
+        let mut vertices = Vec::new();
+        let mut ranges = Vec::with_capacity(frame.sprites.len());
+        for batch in &frame.sprites {
+            let start = vertices.len() as u32;
+            vertices.extend(batch.indices.iter().map(|&index| {
+                let vertex = &batch.vertices[index as usize];
+                GpuVertex {
+                    position: vertex.position.to_array(),
+                    normal: [0.0; 3],
+                    uv: vertex.uv.to_array(),
+                    color: vertex.color.to_array(),
+                }
+            }));
+            ranges.push((batch, start, vertices.len() as u32 - start));
+        }
+        let sprite_buffer = self
+            .sprite_buffer
+            .upload(&self.device, &self.context, &vertices)?;
+        self.bind_vertex_buffer(sprite_buffer);
+        self.context
+            .IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+
+        let screen_projection = Mat4::orthographic_rh(
+            0.0,
+            self.width as f32,
+            self.height as f32,
+            0.0,
+            -1.0,
+            1.0,
+        );
+        for space in [SpriteSpace::World, SpriteSpace::Screen] {
+            let (projection, depth_view) = match space {
+                SpriteSpace::World => (frame.view_projection, Some(&self.depth_view)),
+                SpriteSpace::Screen => (screen_projection, None),
+            };
+            self.context
+                .OMSetRenderTargets(Some(&[Some(self.render_target.clone())]), depth_view);
+            let batches = ranges.iter().filter(|(batch, ..)| batch.space == space);
+            for &(batch, start, count) in batches {
+                let material = batch.material();
+                self.bind_material(&material);
+                self.update_constants(&ConstantBuffer {
+                    mvp: projection.to_cols_array_2d(),
+                    tint: [1.0; 4],
+                    params: material_params(&material),
+                });
+                self.context.Draw(count, start);
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn bind_vertex_buffer(&self, buffer: ID3D11Buffer) {
+        let buffers = [Some(buffer)];
+        let strides = [size_of::<GpuVertex>() as u32];
+        let offsets = [0u32];
+        self.context.IASetVertexBuffers(
+            0,
+            1,
+            Some(buffers.as_ptr()),
+            Some(strides.as_ptr()),
+            Some(offsets.as_ptr()),
+        );
+    }
+
+    unsafe fn bind_material(&self, material: &Material) {
+        let texture = material
+            .texture
+            .and_then(|texture| self.textures.get(&texture))
+            .unwrap_or(&self.white_texture);
+        self.context
+            .PSSetShaderResources(0, Some(&[Some(texture.clone())]));
+        self.context
+            .PSSetSamplers(0, Some(&[self.samplers.get(&material.sampler).cloned()]));
+        self.context.RSSetState(if material.two_sided {
+            &self.states.cull_none
+        } else {
+            &self.states.cull_back
+        });
+        if material.is_blended() {
+            self.context
+                .OMSetBlendState(&self.states.alpha_blend, None, u32::MAX);
+            self.context
+                .OMSetDepthStencilState(&self.states.depth_read_only, 0);
+        } else {
+            self.context.OMSetBlendState(None, None, u32::MAX);
+            self.context.OMSetDepthStencilState(None, 0);
+        }
+    }
+
+    unsafe fn update_constants(&self, constants: &ConstantBuffer) {
+        self.context.UpdateSubresource(
+            &self.constant_buffer,
+            0,
+            None,
+            constants as *const ConstantBuffer as *const _,
+            0,
+            0,
+        );
+    }
+
+    unsafe fn draw_mesh(&self, mesh: &GpuMesh) {
+        self.bind_vertex_buffer(mesh.vertex_buffer.clone());
+        self.context
+            .IASetIndexBuffer(&mesh.index_buffer, DXGI_FORMAT_R32_UINT, 0);
+        self.context
+            .IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+        for submesh in &mesh.submeshes {
+            self.context.DrawIndexed(submesh.count, submesh.start, 0);
+        }
+    }
 }
 
 #[cfg(target_os = "windows")]
 #[repr(C)]
 #[derive(Copy, Clone)]
-struct Vertex {
+struct GpuVertex {
     position: [f32; 3],
-    color: [f32; 3],
+    normal: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
 }
 
 #[cfg(target_os = "windows")]
-#[repr(C)]
-#[derive(Copy, Clone)]
-struct ConstantBuffer {
-    mvp: [[f32; 4]; 4],
+struct GpuMesh {
+    vertex_buffer: ID3D11Buffer,
+    index_buffer: ID3D11Buffer,
+    submeshes: Vec<Submesh>,
 }
 
 #[cfg(target_os = "windows")]
-struct ShaderBundle {
-    vertex_shader: ID3D11VertexShader,
-    pixel_shader: ID3D11PixelShader,
-    input_layout: ID3D11InputLayout,
+fn line_vertices(lines: &[DebugLine]) -> Vec<GpuVertex> {
+    lines
+        .iter()
+        .flat_map(|line| {
+            [line.start, line.end].map(|position| GpuVertex {
+                position: position.to_array(),
+                normal: [0.0; 3],
+                uv: [0.0; 2],
+                color: line.color.to_array(),
+            })
+        })
+        .collect()
 }
 
 #[cfg(target_os = "windows")]
-struct CubeBuffers {
-    vertex_buffer: ID3D11Buffer,
-    index_buffer: ID3D11Buffer,
-    constant_buffer: ID3D11Buffer,
+fn mesh_vertices(mesh: &Mesh) -> Vec<GpuVertex> {
+    mesh.positions
+        .iter()
+        .enumerate()
+        .map(|(index, position)| GpuVertex {
+            position: position.to_array(),
+            normal: mesh.normals.get(index).map_or([0.0; 3], |n| n.to_array()),
+            uv: mesh.uvs.get(index).map_or([0.0; 2], |uv| uv.to_array()),
+            color: mesh.colors.get(index).map_or([1.0; 4], |c| c.to_array()),
+        })
+        .collect()
 }
 
 #[cfg(target_os = "windows")]
-const CUBE_INDEX_COUNT: u32 = 36;
+#[derive(Default)]
+struct DynamicBuffer {
+    buffer: Option<ID3D11Buffer>,
+    capacity: usize,
+}
 
 #[cfg(target_os = "windows")]
-fn cube_vertices() -> [Vertex; 8] {
-    [
-        Vertex {
-            position: [-1.0, -1.0, -1.0],
-            color: [1.0, 0.0, 0.0],
-        },
-        Vertex {
-            position: [1.0, -1.0, -1.0],
-            color: [0.0, 1.0, 0.0],
-        },
-        Vertex {
-            position: [1.0, 1.0, -1.0],
-            color: [0.0, 0.0, 1.0],
-        },
-        Vertex {
-            position: [-1.0, 1.0, -1.0],
-            color: [1.0, 1.0, 0.0],
-        },
-        Vertex {
-            position: [-1.0, -1.0, 1.0],
-            color: [1.0, 0.0, 1.0],
-        },
-        Vertex {
-            position: [1.0, -1.0, 1.0],
-            color: [0.0, 1.0, 1.0],
-        },
-        Vertex {
-            position: [1.0, 1.0, 1.0],
-            color: [1.0, 1.0, 1.0],
-        },
-        Vertex {
-            position: [-1.0, 1.0, 1.0],
-            color: [0.1, 0.6, 0.9],
-        },
-    ]
+impl DynamicBuffer {
+    unsafe fn upload(
+        &mut self,
+        device: &ID3D11Device,
+        context: &ID3D11DeviceContext,
+        vertices: &[GpuVertex],
+    ) -> Result<ID3D11Buffer, EngineError> {
+        let buffer = match self.buffer.clone() {
+            Some(buffer) if self.capacity >= vertices.len() => buffer,
+            _ => {
+                let capacity = vertices.len().next_power_of_two();
+                let buffer = create_dynamic_buffer(device, capacity)?;
+                self.buffer = Some(buffer.clone());
+                self.capacity = capacity;
+                buffer
+            }
+        };
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        context
+            .Map(&buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped))
+            .map_err(|err| EngineError::Runtime(format!("map dynamic buffer: {err:?}")))?;
+        std::ptr::copy_nonoverlapping(
+            vertices.as_ptr(),
+            mapped.pData as *mut GpuVertex,
+            vertices.len(),
+        );
+        context.Unmap(&buffer, 0);
+        Ok(buffer)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn create_dynamic_buffer(
+    device: &ID3D11Device,
+    capacity: usize,
+) -> Result<ID3D11Buffer, EngineError> {
+    let desc = D3D11_BUFFER_DESC {
+        ByteWidth: (size_of::<GpuVertex>() * capacity) as u32,
+        Usage: D3D11_USAGE_DYNAMIC,
+        BindFlags: D3D11_BIND_VERTEX_BUFFER.0 as u32,
+        CPUAccessFlags: D3D11_CPU_ACCESS_WRITE.0 as u32,
+        ..Default::default()
+    };
+    unsafe {
+        let mut buffer = None;
+        device
+            .CreateBuffer(&desc, None, Some(&mut buffer))
+            .map_err(|err| EngineError::Runtime(format!("dynamic buffer: {err:?}")))?;
+        buffer.ok_or_else(|| EngineError::Runtime("missing dynamic buffer".to_string()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct ConstantBuffer {
+    mvp: [[f32; 4]; 4],
+    tint: [f32; 4],
+    // x: alpha cutoff (negative disables), y: 1.0 forces opaque output alpha.
+    params: [f32; 4],
 }
 
 #[cfg(target_os = "windows")]
-fn cube_indices() -> [u16; CUBE_INDEX_COUNT as usize] {
-    [
-        0, 1, 2, 0, 2, 3, 4, 6, 5, 4, 7, 6, 4, 5, 1, 4, 1, 0, 3, 2, 6, 3, 6, 7, 1,
-        5, 6, 1, 6, 2, 4, 0, 3, 4, 3, 7,
-    ]
+fn material_params(material: &Material) -> [f32; 4] {
+    let opaque = if material.is_blended() { 0.0 } else { 1.0 };
+    [material.alpha_cutoff().unwrap_or(-1.0), opaque, 0.0, 0.0]
+}
+
+#[cfg(target_os = "windows")]
+struct PipelineStates {
+    cull_back: ID3D11RasterizerState,
+    cull_none: ID3D11RasterizerState,
+    alpha_blend: ID3D11BlendState,
+    depth_read_only: ID3D11DepthStencilState,
+}
+
+#[cfg(target_os = "windows")]
+struct ShaderBundle {
+    vertex_shader: ID3D11VertexShader,
+    pixel_shader: ID3D11PixelShader,
+    input_layout: ID3D11InputLayout,
 }
 
 #[cfg(target_os = "windows")]
@@ -306,7 +595,7 @@ fn window_handle(window: &winit::window::Window) -> Result<HWND, EngineError> {
         .map_err(|err| EngineError::WindowCreation(format!("window handle: {err:?}")))?;
     match handle.as_raw() {
         RawWindowHandle::Win32(handle) => {
-            let hwnd = handle.hwnd.get() as isize;
+            let hwnd = handle.hwnd.get();
             Ok(HWND(hwnd))
         }
         _ => Err(EngineError::UnsupportedPlatform(
@@ -316,21 +605,127 @@ fn window_handle(window: &winit::window::Window) -> Result<HWND, EngineError> {
 }
 
 #[cfg(target_os = "windows")]
-fn create_render_target(
+fn create_device_and_swap_chain(
+    window: &winit::window::Window,
+    width: u32,
+    height: u32,
+) -> Result<(ID3D11Device, ID3D11DeviceContext, IDXGISwapChain), EngineError> {
+    let hwnd = window_handle(window)?;
+    let swap_chain_desc = DXGI_SWAP_CHAIN_DESC {
+        BufferDesc: DXGI_MODE_DESC {
+            Width: width,
+            Height: height,
+            Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+            ..Default::default()
+        },
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        BufferUsage: windows::Win32::Graphics::Dxgi::DXGI_USAGE_RENDER_TARGET_OUTPUT,
+        BufferCount: 2,
+        OutputWindow: hwnd,
+        Windowed: true.into(),
+        SwapEffect: DXGI_SWAP_EFFECT_DISCARD,
+        ..Default::default()
+    };
+
+    let mut device = None;
+    let mut context = None;
+    let mut swap_chain = None;
+
+    unsafe {
+        D3D11CreateDeviceAndSwapChain(
+            None,
+            D3D_DRIVER_TYPE_HARDWARE,
+            None,
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            Some(&[D3D_FEATURE_LEVEL_11_0]),
+            D3D11_SDK_VERSION,
+            Some(&swap_chain_desc),
+            Some(&mut swap_chain),
+            Some(&mut device),
+            None,
+            Some(&mut context),
+        )
+        .map_err(|err| EngineError::RendererInit(format!("D3D11 init failed: {err:?}")))?;
+    }
+
+    let device = device.ok_or_else(|| {
+        EngineError::RendererInit("missing D3D11 device".to_string())
+    })?;
+    let context = context.ok_or_else(|| {
+        EngineError::RendererInit("missing D3D11 context".to_string())
+    })?;
+    let swap_chain = swap_chain.ok_or_else(|| {
+        EngineError::RendererInit("missing swap chain".to_string())
+    })?;
+    Ok((device, context, swap_chain))
+}
+
+#[cfg(target_os = "windows")]
+fn create_device() -> Result<(ID3D11Device, ID3D11DeviceContext), EngineError> {
+    let mut device = None;
+    let mut context = None;
+    unsafe {
+        D3D11CreateDevice(
+            None,
+            D3D_DRIVER_TYPE_HARDWARE,
+            None,
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            Some(&[D3D_FEATURE_LEVEL_11_0]),
+            D3D11_SDK_VERSION,
+            Some(&mut device),
+            None,
+            Some(&mut context),
+        )
+        .map_err(|err| EngineError::RendererInit(format!("D3D11 init failed: {err:?}")))?;
+    }
+    let device = device.ok_or_else(|| {
+        EngineError::RendererInit("missing D3D11 device".to_string())
+    })?;
+    let context = context.ok_or_else(|| {
+        EngineError::RendererInit("missing D3D11 context".to_string())
+    })?;
+    Ok((device, context))
+}
+
+#[cfg(target_os = "windows")]
+fn create_color_target(
     device: &ID3D11Device,
-    swap_chain: &IDXGISwapChain,
-) -> Result<ID3D11RenderTargetView, EngineError> {
+    width: u32,
+    height: u32,
+) -> Result<(ID3D11Texture2D, ID3D11RenderTargetView), EngineError> {
+    let desc = D3D11_TEXTURE2D_DESC {
+        Width: width,
+        Height: height,
+        MipLevels: 1,
+        ArraySize: 1,
+        Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        Usage: D3D11_USAGE_DEFAULT,
+        BindFlags: D3D11_BIND_RENDER_TARGET.0 as u32,
+        ..Default::default()
+    };
     unsafe {
-        let back_buffer: ID3D11Texture2D = swap_chain
-            .GetBuffer(0)
-            .map_err(|err| EngineError::RendererInit(format!("back buffer: {err:?}")))?;
+        let mut color_texture = None;
+        device
+            .CreateTexture2D(&desc, None, Some(&mut color_texture))
+            .map_err(|err| EngineError::RendererInit(format!("color texture: {err:?}")))?;
+        let color_texture: ID3D11Texture2D = color_texture.ok_or_else(|| {
+            EngineError::RendererInit("missing color texture".to_string())
+        })?;
         let mut render_target = None;
         device
-            .CreateRenderTargetView(&back_buffer, None, Some(&mut render_target))
+            .CreateRenderTargetView(&color_texture, None, Some(&mut render_target))
             .map_err(|err| EngineError::RendererInit(format!("rtv: {err:?}")))?;
-        render_target.ok_or_else(|| {
+        let render_target = render_target.ok_or_else(|| {
             EngineError::RendererInit("missing render target".to_string())
-        })
+        })?;
+        Ok((color_texture, render_target))
     }
 }
 
@@ -393,29 +788,45 @@ fn create_shaders(device: &ID3D11Device) -> Result<ShaderBundle, EngineError> {
     let vertex_source = r#"
 cbuffer Frame : register(b0) {
     float4x4 mvp;
+    float4 tint;
+    float4 params;
 };
 struct VSInput {
     float3 position : POSITION;
-    float3 color : COLOR;
+    float3 normal : NORMAL;
+    float2 uv : TEXCOORD;
+    float4 color : COLOR;
 };
 struct VSOutput {
     float4 position : SV_POSITION;
-    float3 color : COLOR;
+    float4 color : COLOR;
+    float2 uv : TEXCOORD;
 };
 VSOutput main(VSInput input) {
     VSOutput output;
     output.position = mul(mvp, float4(input.position, 1.0));
-    output.color = input.color;
+    output.color = input.color * tint;
+    output.uv = input.uv;
     return output;
 }
 "#;
     let pixel_source = r#"
+cbuffer Frame : register(b0) {
+    float4x4 mvp;
+    float4 tint;
+    float4 params;
+};
+Texture2D base_texture : register(t0);
+SamplerState base_sampler : register(s0);
 struct PSInput {
     float4 position : SV_POSITION;
-    float3 color : COLOR;
+    float4 color : COLOR;
+    float2 uv : TEXCOORD;
 };
 float4 main(PSInput input) : SV_TARGET {
-    return float4(input.color, 1.0);
+    float4 color = input.color * base_texture.Sample(base_sampler, input.uv);
+    clip(color.a - params.x);
+    return float4(color.rgb, params.y > 0.0 ? 1.0 : color.a);
 }
 "#;
     let vertex_blob = compile_shader(vertex_source, "main", "vs_5_0")?;
@@ -448,7 +859,7 @@ float4 main(PSInput input) : SV_TARGET {
 
         let input_elements = [
             D3D11_INPUT_ELEMENT_DESC {
-                SemanticName: PCSTR(b"POSITION\0".as_ptr()),
+                SemanticName: PCSTR(c"POSITION".as_ptr().cast()),
                 SemanticIndex: 0,
                 Format: DXGI_FORMAT_R32G32B32_FLOAT,
                 InputSlot: 0,
@@ -457,7 +868,7 @@ float4 main(PSInput input) : SV_TARGET {
                 InstanceDataStepRate: 0,
             },
             D3D11_INPUT_ELEMENT_DESC {
-                SemanticName: PCSTR(b"COLOR\0".as_ptr()),
+                SemanticName: PCSTR(c"NORMAL".as_ptr().cast()),
                 SemanticIndex: 0,
                 Format: DXGI_FORMAT_R32G32B32_FLOAT,
                 InputSlot: 0,
@@ -465,6 +876,24 @@ float4 main(PSInput input) : SV_TARGET {
                 InputSlotClass: D3D11_INPUT_PER_VERTEX_DATA,
                 InstanceDataStepRate: 0,
             },
+            D3D11_INPUT_ELEMENT_DESC {
+                SemanticName: PCSTR(c"TEXCOORD".as_ptr().cast()),
+                SemanticIndex: 0,
+                Format: DXGI_FORMAT_R32G32_FLOAT,
+                InputSlot: 0,
+                AlignedByteOffset: 24,
+                InputSlotClass: D3D11_INPUT_PER_VERTEX_DATA,
+                InstanceDataStepRate: 0,
+            },
+            D3D11_INPUT_ELEMENT_DESC {
+                SemanticName: PCSTR(c"COLOR".as_ptr().cast()),
+                SemanticIndex: 0,
+                Format: DXGI_FORMAT_R32G32B32A32_FLOAT,
+                InputSlot: 0,
+                AlignedByteOffset: 32,
+                InputSlotClass: D3D11_INPUT_PER_VERTEX_DATA,
+                InstanceDataStepRate: 0,
+            },
         ];
         let mut input_layout = None;
         device
@@ -525,11 +954,13 @@ fn compile_shader(
 }
 
 #[cfg(target_os = "windows")]
-fn create_cube_buffers(device: &ID3D11Device) -> Result<CubeBuffers, EngineError> {
-    let vertices = cube_vertices();
-    let indices = cube_indices();
+fn create_gpu_mesh(device: &ID3D11Device, mesh: &Mesh) -> Result<GpuMesh, EngineError> {
+    let vertices = mesh_vertices(mesh);
+    if vertices.is_empty() || mesh.indices.is_empty() {
+        return Err(EngineError::Runtime("cannot upload an empty mesh".to_string()));
+    }
     let vertex_buffer_desc = D3D11_BUFFER_DESC {
-        ByteWidth: (size_of::<Vertex>() * vertices.len()) as u32,
+        ByteWidth: (size_of::<GpuVertex>() * vertices.len()) as u32,
         Usage: D3D11_USAGE_DEFAULT,
         BindFlags: D3D11_BIND_VERTEX_BUFFER.0 as u32,
         ..Default::default()
@@ -540,20 +971,13 @@ fn create_cube_buffers(device: &ID3D11Device) -> Result<CubeBuffers, EngineError
     };
 
     let index_buffer_desc = D3D11_BUFFER_DESC {
-        ByteWidth: (size_of::<u16>() * indices.len()) as u32,
+        ByteWidth: (size_of::<u32>() * mesh.indices.len()) as u32,
         Usage: D3D11_USAGE_DEFAULT,
         BindFlags: D3D11_BIND_INDEX_BUFFER.0 as u32,
         ..Default::default()
     };
     let index_data = D3D11_SUBRESOURCE_DATA {
-        pSysMem: indices.as_ptr() as *const _,
-        ..Default::default()
-    };
-
-    let constant_buffer_desc = D3D11_BUFFER_DESC {
-        ByteWidth: size_of::<ConstantBuffer>() as u32,
-        Usage: D3D11_USAGE_DEFAULT,
-        BindFlags: D3D11_BIND_CONSTANT_BUFFER.0 as u32,
+        pSysMem: mesh.indices.as_ptr() as *const _,
         ..Default::default()
     };
 
@@ -561,41 +985,179 @@ fn create_cube_buffers(device: &ID3D11Device) -> Result<CubeBuffers, EngineError
         let mut vertex_buffer = None;
         device
             .CreateBuffer(&vertex_buffer_desc, Some(&vertex_data), Some(&mut vertex_buffer))
-            .map_err(|err| EngineError::RendererInit(format!("vertex buffer: {err:?}")))?;
+            .map_err(|err| EngineError::Runtime(format!("vertex buffer: {err:?}")))?;
         let vertex_buffer = vertex_buffer.ok_or_else(|| {
-            EngineError::RendererInit("missing vertex buffer".to_string())
+            EngineError::Runtime("missing vertex buffer".to_string())
         })?;
 
         let mut index_buffer = None;
         device
             .CreateBuffer(&index_buffer_desc, Some(&index_data), Some(&mut index_buffer))
-            .map_err(|err| EngineError::RendererInit(format!("index buffer: {err:?}")))?;
+            .map_err(|err| EngineError::Runtime(format!("index buffer: {err:?}")))?;
         let index_buffer = index_buffer.ok_or_else(|| {
-            EngineError::RendererInit("missing index buffer".to_string())
+            EngineError::Runtime("missing index buffer".to_string())
         })?;
 
+        Ok(GpuMesh {
+            vertex_buffer,
+            index_buffer,
+            submeshes: mesh.submesh_ranges(),
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn create_constant_buffer(device: &ID3D11Device) -> Result<ID3D11Buffer, EngineError> {
+    let constant_buffer_desc = D3D11_BUFFER_DESC {
+        ByteWidth: size_of::<ConstantBuffer>() as u32,
+        Usage: D3D11_USAGE_DEFAULT,
+        BindFlags: D3D11_BIND_CONSTANT_BUFFER.0 as u32,
+        ..Default::default()
+    };
+    unsafe {
         let mut constant_buffer = None;
         device
             .CreateBuffer(&constant_buffer_desc, None, Some(&mut constant_buffer))
             .map_err(|err| EngineError::RendererInit(format!("constant buffer: {err:?}")))?;
-        let constant_buffer = constant_buffer.ok_or_else(|| {
-            EngineError::RendererInit("missing constant buffer".to_string())
-        })?;
+        constant_buffer
+            .ok_or_else(|| EngineError::RendererInit("missing constant buffer".to_string()))
+    }
+}
 
-        Ok(CubeBuffers {
-            vertex_buffer,
-            index_buffer,
-            constant_buffer,
-        })
+#[cfg(target_os = "windows")]
+fn create_texture(
+    device: &ID3D11Device,
+    image: &Image,
+) -> Result<ID3D11ShaderResourceView, EngineError> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(EngineError::Runtime("cannot upload an empty texture".to_string()));
     }
+    let desc = D3D11_TEXTURE2D_DESC {
+        Width: image.width(),
+        Height: image.height(),
+        MipLevels: 1,
+        ArraySize: 1,
+        Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        Usage: D3D11_USAGE_IMMUTABLE,
+        BindFlags: D3D11_BIND_SHADER_RESOURCE.0 as u32,
+        ..Default::default()
+    };
+    let data = D3D11_SUBRESOURCE_DATA {
+        pSysMem: image.pixels().as_ptr() as *const _,
+        SysMemPitch: image.width() * 4,
+        ..Default::default()
+    };
+    unsafe {
+        let mut texture = None;
+        device
+            .CreateTexture2D(&desc, Some(&data), Some(&mut texture))
+            .map_err(|err| EngineError::Runtime(format!("texture: {err:?}")))?;
+        let texture: ID3D11Texture2D =
+            texture.ok_or_else(|| EngineError::Runtime("missing texture".to_string()))?;
+        let mut view = None;
+        device
+            .CreateShaderResourceView(&texture, None, Some(&mut view))
+            .map_err(|err| EngineError::Runtime(format!("texture view: {err:?}")))?;
+        view.ok_or_else(|| EngineError::Runtime("missing texture view".to_string()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn create_samplers(
+    device: &ID3D11Device,
+) -> Result<HashMap<Sampler, ID3D11SamplerState>, EngineError> {
+    let mut samplers = HashMap::new();
+    for filter in [FilterMode::Nearest, FilterMode::Linear] {
+        for wrap in [WrapMode::Clamp, WrapMode::Repeat, WrapMode::Mirror] {
+            let address = match wrap {
+                WrapMode::Clamp => D3D11_TEXTURE_ADDRESS_CLAMP,
+                WrapMode::Repeat => D3D11_TEXTURE_ADDRESS_WRAP,
+                WrapMode::Mirror => D3D11_TEXTURE_ADDRESS_MIRROR,
+            };
+            let desc = D3D11_SAMPLER_DESC {
+                Filter: match filter {
+                    FilterMode::Nearest => D3D11_FILTER_MIN_MAG_MIP_POINT,
+                    FilterMode::Linear => D3D11_FILTER_MIN_MAG_MIP_LINEAR,
+                },
+                AddressU: address,
+                AddressV: address,
+                AddressW: address,
+                ComparisonFunc: D3D11_COMPARISON_NEVER,
+                MaxLOD: D3D11_FLOAT32_MAX,
+                ..Default::default()
+            };
+            let mut sampler = None;
+            unsafe {
+                device
+                    .CreateSamplerState(&desc, Some(&mut sampler))
+                    .map_err(|err| EngineError::RendererInit(format!("sampler: {err:?}")))?;
+            }
+            let sampler = sampler
+                .ok_or_else(|| EngineError::RendererInit("missing sampler".to_string()))?;
+            samplers.insert(Sampler::new(filter, wrap), sampler);
+        }
+    }
+    Ok(samplers)
 }
 
 #[cfg(target_os = "windows")]
-fn cube_transform(time_seconds: f32, width: u32, height: u32) -> Mat4 {
-    let aspect = width as f32 / height.max(1) as f32;
-    let projection = Mat4::perspective_rh(45.0_f32.to_radians(), aspect, 0.1, 100.0);
-    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO, Vec3::Y);
-    let rotation =
-        Mat4::from_rotation_y(time_seconds * 0.8) * Mat4::from_rotation_x(time_seconds * 0.6);
-    projection * view * rotation
+fn create_pipeline_states(device: &ID3D11Device) -> Result<PipelineStates, EngineError> {
+    let rasterizer = |cull_mode| {
+        let desc = D3D11_RASTERIZER_DESC {
+            FillMode: D3D11_FILL_SOLID,
+            CullMode: cull_mode,
+            DepthClipEnable: true.into(),
+            ..Default::default()
+        };
+        let mut state = None;
+        unsafe {
+            device
+                .CreateRasterizerState(&desc, Some(&mut state))
+                .map_err(|err| EngineError::RendererInit(format!("rasterizer state: {err:?}")))?;
+        }
+        state.ok_or_else(|| EngineError::RendererInit("missing rasterizer state".to_string()))
+    };
+    let cull_back = rasterizer(D3D11_CULL_BACK)?;
+    let cull_none = rasterizer(D3D11_CULL_NONE)?;
+
+    let mut blend_desc = D3D11_BLEND_DESC::default();
+    // Output alpha is left untouched so captures stay opaque like the software backend.
+    blend_desc.RenderTarget[0] = D3D11_RENDER_TARGET_BLEND_DESC {
+        BlendEnable: true.into(),
+        SrcBlend: D3D11_BLEND_SRC_ALPHA,
+        DestBlend: D3D11_BLEND_INV_SRC_ALPHA,
+        BlendOp: D3D11_BLEND_OP_ADD,
+        SrcBlendAlpha: D3D11_BLEND_ZERO,
+        DestBlendAlpha: D3D11_BLEND_ONE,
+        BlendOpAlpha: D3D11_BLEND_OP_ADD,
+        RenderTargetWriteMask: D3D11_COLOR_WRITE_ENABLE_ALL.0 as u8,
+    };
+    let depth_desc = D3D11_DEPTH_STENCIL_DESC {
+        DepthEnable: true.into(),
+        DepthWriteMask: D3D11_DEPTH_WRITE_MASK_ZERO,
+        DepthFunc: D3D11_COMPARISON_LESS,
+        ..Default::default()
+    };
+    let mut alpha_blend = None;
+    let mut depth_read_only = None;
+    unsafe {
+        device
+            .CreateBlendState(&blend_desc, Some(&mut alpha_blend))
+            .map_err(|err| EngineError::RendererInit(format!("blend state: {err:?}")))?;
+        device
+            .CreateDepthStencilState(&depth_desc, Some(&mut depth_read_only))
+            .map_err(|err| EngineError::RendererInit(format!("depth state: {err:?}")))?;
+    }
+    Ok(PipelineStates {
+        cull_back,
+        cull_none,
+        alpha_blend: alpha_blend
+            .ok_or_else(|| EngineError::RendererInit("missing blend state".to_string()))?,
+        depth_read_only: depth_read_only
+            .ok_or_else(|| EngineError::RendererInit("missing depth state".to_string()))?,
+    })
 }
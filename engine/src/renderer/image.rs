@@ -0,0 +1,196 @@
+use crate::error::EngineError;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn new(width: u32, height: u32) -> Result<Self, EngineError> {
+        Ok(Self {
+            width,
+            height,
+            pixels: vec![0; byte_len(width, height)?],
+        })
+    }
+
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, EngineError> {
+        let expected = byte_len(width, height)?;
+        if pixels.len() != expected {
+            return Err(EngineError::Image(format!(
+                "expected {expected} bytes for a {width}x{height} RGBA image, got {}",
+                pixels.len()
+            )));
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = self.index(x, y);
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[index..index + 4]);
+        pixel
+    }
+
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: [u8; 4]) {
+        let index = self.index(x, y);
+        self.pixels[index..index + 4].copy_from_slice(&pixel);
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>, EngineError> {
+        let mut bytes = Vec::new();
+        self.write_png(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), EngineError> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .map_err(|err| EngineError::Io(format!("{}: {err}", path.display())))?;
+        self.write_png(BufWriter::new(file))
+    }
+
+    pub fn decode_png(bytes: &[u8]) -> Result<Self, EngineError> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder
+            .read_info()
+            .map_err(|err| EngineError::Image(format!("png decode: {err}")))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|err| EngineError::Image(format!("png decode: {err}")))?;
+        buffer.truncate(info.buffer_size());
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            png::ColorType::Grayscale => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::Indexed => {
+                return Err(EngineError::Image(
+                    "png decode: unexpanded indexed color".to_string(),
+                ))
+            }
+        };
+        Self::from_rgba(info.width, info.height, pixels)
+    }
+
+    pub fn decode_jpeg(bytes: &[u8]) -> Result<Self, EngineError> {
+        let mut decoder = jpeg_decoder::Decoder::new(bytes);
+        let buffer = decoder
+            .decode()
+            .map_err(|err| EngineError::Image(format!("jpeg decode: {err}")))?;
+        let info = decoder
+            .info()
+            .ok_or_else(|| EngineError::Image("jpeg decode: missing header".to_string()))?;
+        let pixels = match info.pixel_format {
+            jpeg_decoder::PixelFormat::RGB24 => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            jpeg_decoder::PixelFormat::L8 => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            jpeg_decoder::PixelFormat::L16 => buffer
+                .chunks_exact(2)
+                .flat_map(|g| {
+                    let g = (u16::from_ne_bytes([g[0], g[1]]) >> 8) as u8;
+                    [g, g, g, 255]
+                })
+                .collect(),
+            jpeg_decoder::PixelFormat::CMYK32 => buffer
+                .chunks_exact(4)
+                .flat_map(|cmyk| {
+                    let k = 255 - cmyk[3] as u32;
+                    let channel = |c: u8| ((255 - c as u32) * k / 255) as u8;
+                    [channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2]), 255]
+                })
+                .collect(),
+        };
+        Self::from_rgba(info.width as u32, info.height as u32, pixels)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, EngineError> {
+        if bytes.starts_with(b"\x89PNG") {
+            Self::decode_png(bytes)
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            Self::decode_jpeg(bytes)
+        } else {
+            Err(EngineError::Image(
+                "unrecognized image format (expected PNG or JPEG)".to_string(),
+            ))
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|err| EngineError::Io(format!("{}: {err}", path.display())))?;
+        Self::decode(&bytes)
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|err| EngineError::Io(format!("{}: {err}", path.display())))?;
+        Self::decode_png(&bytes)
+    }
+
+    fn write_png(&self, writer: impl std::io::Write) -> Result<(), EngineError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|err| EngineError::Image(format!("png encode: {err}")))?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|err| EngineError::Image(format!("png encode: {err}")))
+    }
+}
+
+// Sizes come straight from file headers, so the byte count is checked rather
+// than trusted to fit.
+fn byte_len(width: u32, height: u32) -> Result<usize, EngineError> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .ok_or_else(|| EngineError::Image(format!("{width}x{height} RGBA image is too large")))
+}
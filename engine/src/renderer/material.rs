@@ -0,0 +1,91 @@
+use crate::assets::Handle;
+use crate::renderer::Image;
+use glam::Vec4;
+
+pub type MaterialHandle = Handle<Material>;
+pub type TextureHandle = Handle<Image>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FilterMode {
+    Nearest,
+    #[default]
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WrapMode {
+    #[default]
+    Clamp,
+    Repeat,
+    Mirror,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sampler {
+    pub filter: FilterMode,
+    pub wrap: WrapMode,
+}
+
+impl Sampler {
+    pub const fn new(filter: FilterMode, wrap: WrapMode) -> Self {
+        Self { filter, wrap }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AlphaMode {
+    #[default]
+    Opaque,
+    Mask {
+        cutoff: f32,
+    },
+    Blend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Material {
+    pub base_color: Vec4,
+    pub texture: Option<TextureHandle>,
+    pub sampler: Sampler,
+    pub alpha_mode: AlphaMode,
+    pub two_sided: bool,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            base_color: Vec4::ONE,
+            texture: None,
+            sampler: Sampler::default(),
+            alpha_mode: AlphaMode::Opaque,
+            two_sided: false,
+        }
+    }
+}
+
+impl Material {
+    pub fn color(base_color: Vec4) -> Self {
+        Self {
+            base_color,
+            ..Self::default()
+        }
+    }
+
+    pub fn textured(texture: TextureHandle) -> Self {
+        Self {
+            texture: Some(texture),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_blended(&self) -> bool {
+        self.alpha_mode == AlphaMode::Blend
+    }
+
+    pub(crate) fn alpha_cutoff(&self) -> Option<f32> {
+        match self.alpha_mode {
+            AlphaMode::Mask { cutoff } => Some(cutoff),
+            _ => None,
+        }
+    }
+}
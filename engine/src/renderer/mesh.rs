@@ -0,0 +1,182 @@
+use crate::assets::Handle;
+use crate::error::EngineError;
+use glam::{Vec2, Vec3, Vec4};
+use std::f32::consts::{PI, TAU};
+
+pub type MeshHandle = Handle<Mesh>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submesh {
+    pub start: u32,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<Vec2>,
+    pub colors: Vec<Vec4>,
+    pub indices: Vec<u32>,
+    pub submeshes: Vec<Submesh>,
+}
+
+impl Mesh {
+    pub fn new(positions: Vec<Vec3>, indices: Vec<u32>) -> Self {
+        Self {
+            positions,
+            indices,
+            ..Self::default()
+        }
+    }
+
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        // (normal, u, v) with u x v == normal, so each face's corners run
+        // counter-clockwise when seen from outside.
+        let faces = [
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+        ];
+        let mut mesh = Self::default();
+        for (normal, u, v) in faces {
+            let base = mesh.positions.len() as u32;
+            for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                mesh.positions
+                    .push((normal + u * su + v * sv) * half_extents);
+                mesh.normals.push(normal);
+                mesh.uvs.push(Vec2::new((su + 1.0) * 0.5, (1.0 - sv) * 0.5));
+            }
+            mesh.indices
+                .extend([base, base + 2, base + 1, base, base + 3, base + 2]);
+        }
+        mesh.with_color(Vec4::ONE)
+    }
+
+    pub fn quad(size: Vec2) -> Self {
+        let half = size * 0.5;
+        let positions = vec![
+            Vec3::new(-half.x, -half.y, 0.0),
+            Vec3::new(half.x, -half.y, 0.0),
+            Vec3::new(half.x, half.y, 0.0),
+            Vec3::new(-half.x, half.y, 0.0),
+        ];
+        Self {
+            positions,
+            normals: vec![Vec3::Z; 4],
+            uvs: vec![
+                Vec2::new(0.0, 1.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(0.0, 0.0),
+            ],
+            indices: vec![0, 2, 1, 0, 3, 2],
+            ..Self::default()
+        }
+        .with_color(Vec4::ONE)
+    }
+
+    pub fn uv_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+        let segments = segments.max(3);
+        let rings = rings.max(2);
+        let mut mesh = Self::default();
+        for ring in 0..=rings {
+            let phi = ring as f32 / rings as f32 * PI;
+            for segment in 0..=segments {
+                let theta = segment as f32 / segments as f32 * TAU;
+                let normal = Vec3::new(phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin());
+                mesh.positions.push(normal * radius);
+                mesh.normals.push(normal);
+                mesh.uvs.push(Vec2::new(
+                    segment as f32 / segments as f32,
+                    ring as f32 / rings as f32,
+                ));
+            }
+        }
+        let stride = segments + 1;
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let a = ring * stride + segment;
+                let b = a + stride;
+                mesh.indices.extend([a, b, a + 1, a + 1, b, b + 1]);
+            }
+        }
+        mesh.with_color(Vec4::ONE)
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.colors = vec![color; self.positions.len()];
+        self
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn index_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn submesh_ranges(&self) -> Vec<Submesh> {
+        if self.submeshes.is_empty() {
+            return vec![Submesh {
+                start: 0,
+                count: self.indices.len() as u32,
+            }];
+        }
+        self.submeshes.clone()
+    }
+
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let first = *self.positions.first()?;
+        Some(
+            self.positions
+                .iter()
+                .fold((first, first), |(min, max), &p| (min.min(p), max.max(p))),
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), EngineError> {
+        let vertex_count = self.positions.len();
+        let attributes = [
+            ("normals", self.normals.len()),
+            ("uvs", self.uvs.len()),
+            ("colors", self.colors.len()),
+        ];
+        for (name, len) in attributes {
+            if len != 0 && len != vertex_count {
+                return Err(EngineError::Asset(format!(
+                    "mesh has {len} {name} for {vertex_count} positions"
+                )));
+            }
+        }
+        if !self.indices.len().is_multiple_of(3) {
+            return Err(EngineError::Asset(format!(
+                "mesh index count {} is not a multiple of 3",
+                self.indices.len()
+            )));
+        }
+        if let Some(index) = self
+            .indices
+            .iter()
+            .find(|&&index| index as usize >= vertex_count)
+        {
+            return Err(EngineError::Asset(format!(
+                "mesh index {index} out of range for {vertex_count} vertices"
+            )));
+        }
+        for submesh in &self.submeshes {
+            let end = submesh.start as usize + submesh.count as usize;
+            if end > self.indices.len() || !submesh.count.is_multiple_of(3) {
+                return Err(EngineError::Asset(format!(
+                    "submesh {submesh:?} does not fit {} indices",
+                    self.indices.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,52 +1,197 @@
+mod animation;
+mod backend;
+mod draw;
 mod dx11;
+mod image;
+mod material;
+mod mesh;
+mod null;
+mod software;
+mod sprite;
 
+use crate::assets::{Assets, Handle};
+use crate::debug_draw::DebugLine;
 use crate::error::EngineError;
-use glam::Vec4;
+use crate::scene::default_clear_color;
+use glam::{Mat4, Vec4};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+pub use animation::{AnimationConfig, AnimationEncoder, AnimationFormat, LoopMode};
+pub use backend::{BackendCapabilities, BackendKind, RenderBackend, RenderTarget};
+pub use draw::{collect_draw_items, DrawItem, MeshRenderer};
+pub(crate) use draw::sorted_draw_items;
+pub use image::Image;
+pub use material::{
+    AlphaMode, FilterMode, Material, MaterialHandle, Sampler, TextureHandle, WrapMode,
+};
+pub use mesh::{Mesh, MeshHandle, Submesh};
+pub use null::NullRenderer;
+pub use software::SoftwareRenderer;
+pub use sprite::{
+    collect_sprite_batches, Sprite, SpriteBatch, SpriteBatcher, SpriteSpace, SpriteVertex, UvRect,
+};
 
 pub struct RenderFrame {
     pub clear_color: Vec4,
     pub time_seconds: f32,
+    pub interpolation_alpha: f32,
+    pub view_projection: Mat4,
+    pub draw_items: Vec<DrawItem>,
+    pub sprites: Vec<SpriteBatch>,
+    pub debug_lines: Vec<DebugLine>,
+}
+
+impl Default for RenderFrame {
+    fn default() -> Self {
+        Self {
+            clear_color: default_clear_color(),
+            time_seconds: 0.0,
+            interpolation_alpha: 0.0,
+            view_projection: Mat4::IDENTITY,
+            draw_items: Vec::new(),
+            sprites: Vec::new(),
+            debug_lines: Vec::new(),
+        }
+    }
 }
 
 pub struct Renderer {
-    #[cfg(target_os = "windows")]
-    inner: dx11::Dx11Renderer,
+    backend: Box<dyn RenderBackend>,
+    uploaded_meshes: HashMap<MeshHandle, u64>,
+    uploaded_textures: HashMap<TextureHandle, u64>,
 }
 
 impl Renderer {
-    pub fn new(window: &winit::window::Window) -> Result<Self, EngineError> {
-        #[cfg(target_os = "windows")]
-        {
-            let inner = dx11::Dx11Renderer::new(window)?;
-            Ok(Self { inner })
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            let _ = window;
-            Err(EngineError::UnsupportedPlatform(
-                "DirectX 11 renderer requires Windows".to_string(),
-            ))
+    pub fn new(target: RenderTarget, backends: &[BackendKind]) -> Result<Self, EngineError> {
+        let mut failures = Vec::new();
+        for &kind in backends {
+            match create_backend(kind, target.clone()) {
+                Ok(backend) => {
+                    info!("using {kind} render backend");
+                    return Ok(Self {
+                        backend,
+                        uploaded_meshes: HashMap::new(),
+                        uploaded_textures: HashMap::new(),
+                    });
+                }
+                Err(err) => {
+                    warn!("{kind} render backend unavailable: {err}");
+                    failures.push(format!("{kind}: {err}"));
+                }
+            }
         }
+        Err(EngineError::RendererInit(format!(
+            "no usable render backend ({})",
+            failures.join("; ")
+        )))
+    }
+
+    pub fn offscreen(
+        width: u32,
+        height: u32,
+        backends: &[BackendKind],
+    ) -> Result<Self, EngineError> {
+        Self::new(RenderTarget::Offscreen { width, height }, backends)
+    }
+
+    pub fn backend_kind(&self) -> BackendKind {
+        self.backend.kind()
+    }
+
+    pub fn capabilities(&self) -> BackendCapabilities {
+        self.backend.capabilities()
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        #[cfg(target_os = "windows")]
-        {
-            self.inner.resize(width, height);
-        }
+        self.backend.resize(width, height);
+    }
+
+    pub fn sync_meshes(&mut self, meshes: &Assets<Mesh>) -> Result<usize, EngineError> {
+        sync_assets(
+            self.backend.as_mut(),
+            &mut self.uploaded_meshes,
+            meshes,
+            |backend, handle, mesh| {
+                mesh.validate()?;
+                backend.upload_mesh(handle, mesh)
+            },
+            |backend, handle| backend.release_mesh(handle),
+        )
     }
 
-    pub fn render(&mut self, frame: RenderFrame) -> Result<(), EngineError> {
-        #[cfg(target_os = "windows")]
-        {
-            self.inner.render(frame)
+    pub fn sync_textures(&mut self, textures: &Assets<Image>) -> Result<usize, EngineError> {
+        sync_assets(
+            self.backend.as_mut(),
+            &mut self.uploaded_textures,
+            textures,
+            |backend, handle, image| backend.upload_texture(handle, image),
+            |backend, handle| backend.release_texture(handle),
+        )
+    }
+
+    pub fn is_mesh_uploaded(&self, handle: MeshHandle) -> bool {
+        self.uploaded_meshes.contains_key(&handle)
+    }
+
+    pub fn is_texture_uploaded(&self, handle: TextureHandle) -> bool {
+        self.uploaded_textures.contains_key(&handle)
+    }
+
+    pub fn render(&mut self, frame: &RenderFrame) -> Result<(), EngineError> {
+        self.backend.render(frame)
+    }
+
+    pub fn capture_frame(&mut self) -> Result<Image, EngineError> {
+        self.backend.capture()
+    }
+}
+
+fn create_backend(
+    kind: BackendKind,
+    target: RenderTarget,
+) -> Result<Box<dyn RenderBackend>, EngineError> {
+    match kind {
+        BackendKind::Dx11 => dx11::create_backend(target),
+        BackendKind::Software => Ok(Box::new(SoftwareRenderer::new(target)?)),
+        BackendKind::Null => Ok(Box::new(NullRenderer::new(target)?)),
+    }
+}
+
+fn sync_assets<T>(
+    backend: &mut dyn RenderBackend,
+    uploaded: &mut HashMap<Handle<T>, u64>,
+    assets: &Assets<T>,
+    upload: impl Fn(&mut dyn RenderBackend, Handle<T>, &T) -> Result<(), EngineError>,
+    release: impl Fn(&mut dyn RenderBackend, Handle<T>),
+) -> Result<usize, EngineError> {
+    let stale: Vec<Handle<T>> = uploaded
+        .keys()
+        .copied()
+        .filter(|&handle| !assets.contains(handle))
+        .collect();
+    for handle in stale {
+        uploaded.remove(&handle);
+        release(backend, handle);
+    }
+    let mut count = 0;
+    let mut first_error = None;
+    for (handle, revision, asset) in assets.iter_revisions() {
+        if uploaded.get(&handle) == Some(&revision) {
+            continue;
         }
-        #[cfg(not(target_os = "windows"))]
-        {
-            let _ = frame;
-            Err(EngineError::UnsupportedPlatform(
-                "DirectX 11 renderer requires Windows".to_string(),
-            ))
+        match upload(backend, handle, asset) {
+            Ok(()) => {
+                uploaded.insert(handle, revision);
+                count += 1;
+            }
+            Err(err) => {
+                first_error.get_or_insert(EngineError::Asset(format!("{handle:?}: {err}")));
+            }
         }
     }
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(count),
+    }
 }
@@ -0,0 +1,48 @@
+use crate::error::EngineError;
+use crate::renderer::backend::{BackendCapabilities, BackendKind, RenderBackend, RenderTarget};
+use crate::renderer::{Image, Mesh, MeshHandle, RenderFrame, TextureHandle};
+
+pub struct NullRenderer;
+
+impl RenderBackend for NullRenderer {
+    fn new(_target: RenderTarget) -> Result<Self, EngineError> {
+        Ok(Self)
+    }
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Null
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            presents_to_window: false,
+            // Nothing is drawn, so there is no image to capture.
+            offscreen: false,
+            hardware_accelerated: false,
+        }
+    }
+
+    fn resize(&mut self, _width: u32, _height: u32) {}
+
+    fn upload_mesh(&mut self, _handle: MeshHandle, _mesh: &Mesh) -> Result<(), EngineError> {
+        Ok(())
+    }
+
+    fn release_mesh(&mut self, _handle: MeshHandle) {}
+
+    fn upload_texture(&mut self, _handle: TextureHandle, _image: &Image) -> Result<(), EngineError> {
+        Ok(())
+    }
+
+    fn release_texture(&mut self, _handle: TextureHandle) {}
+
+    fn render(&mut self, _frame: &RenderFrame) -> Result<(), EngineError> {
+        Ok(())
+    }
+
+    fn capture(&mut self) -> Result<Image, EngineError> {
+        Err(EngineError::Runtime(
+            "null render backend has no framebuffer to capture".to_string(),
+        ))
+    }
+}
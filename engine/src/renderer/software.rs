@@ -0,0 +1,552 @@
+use crate::debug_draw::DebugLine;
+use crate::error::EngineError;
+use crate::renderer::backend::{BackendCapabilities, BackendKind, RenderBackend, RenderTarget};
+use crate::renderer::{
+    sorted_draw_items, FilterMode, Image, Material, Mesh, MeshHandle, RenderFrame,
+    Sampler, SpriteBatch, SpriteSpace, TextureHandle, WrapMode,
+};
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use tracing::warn;
+use winit::window::Window;
+
+pub struct SoftwareRenderer {
+    target: Framebuffer,
+    meshes: HashMap<MeshHandle, Mesh>,
+    textures: HashMap<TextureHandle, Image>,
+    presenter: Option<Presenter>,
+}
+
+struct Presenter {
+    _context: softbuffer::Context<Arc<Window>>,
+    surface: softbuffer::Surface<Arc<Window>, Arc<Window>>,
+}
+
+struct Framebuffer {
+    width: u32,
+    height: u32,
+    color: Vec<u8>,
+    depth: Vec<f32>,
+}
+
+#[derive(Clone, Copy)]
+struct Shading<'a> {
+    material: &'a Material,
+    texture: Option<&'a Image>,
+    depth_test: bool,
+}
+
+#[derive(Clone, Copy)]
+struct ClipVertex {
+    position: Vec4,
+    color: Vec4,
+    uv: Vec2,
+}
+
+#[derive(Clone, Copy)]
+struct ScreenVertex {
+    x: f32,
+    y: f32,
+    z: f32,
+    inv_w: f32,
+    color: Vec4,
+    uv: Vec2,
+}
+
+impl SoftwareRenderer {
+    pub fn offscreen(width: u32, height: u32) -> Result<Self, EngineError> {
+        Ok(Self {
+            target: Framebuffer::new(width.max(1), height.max(1))?,
+            meshes: HashMap::new(),
+            textures: HashMap::new(),
+            presenter: None,
+        })
+    }
+
+    pub fn with_window(window: Arc<Window>) -> Result<Self, EngineError> {
+        let size = window.inner_size();
+        let context = softbuffer::Context::new(window.clone())
+            .map_err(|err| EngineError::RendererInit(format!("softbuffer context: {err}")))?;
+        let surface = softbuffer::Surface::new(&context, window)
+            .map_err(|err| EngineError::RendererInit(format!("softbuffer surface: {err}")))?;
+        let mut renderer = Self::offscreen(size.width, size.height)?;
+        renderer.presenter = Some(Presenter {
+            _context: context,
+            surface,
+        });
+        Ok(renderer)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.target.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.target.height
+    }
+
+    fn present(&mut self) -> Result<(), EngineError> {
+        let Some(presenter) = self.presenter.as_mut() else {
+            return Ok(());
+        };
+        let (Some(width), Some(height)) = (
+            NonZeroU32::new(self.target.width),
+            NonZeroU32::new(self.target.height),
+        ) else {
+            return Ok(());
+        };
+        presenter
+            .surface
+            .resize(width, height)
+            .map_err(|err| EngineError::Runtime(format!("softbuffer resize: {err}")))?;
+        let mut buffer = presenter
+            .surface
+            .buffer_mut()
+            .map_err(|err| EngineError::Runtime(format!("softbuffer buffer: {err}")))?;
+        for (target, pixel) in buffer.iter_mut().zip(self.target.color.chunks_exact(4)) {
+            *target = (pixel[0] as u32) << 16 | (pixel[1] as u32) << 8 | pixel[2] as u32;
+        }
+        buffer
+            .present()
+            .map_err(|err| EngineError::Runtime(format!("present failed: {err}")))
+    }
+}
+
+impl Framebuffer {
+    fn new(width: u32, height: u32) -> Result<Self, EngineError> {
+        let pixel_count = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|count| count.checked_mul(4).is_some())
+            .ok_or_else(|| {
+                EngineError::RendererInit(format!("{width}x{height} framebuffer is too large"))
+            })?;
+        Ok(Self {
+            width,
+            height,
+            color: vec![0; pixel_count * 4],
+            depth: vec![1.0; pixel_count],
+        })
+    }
+
+    fn clear(&mut self, clear_color: Vec4) {
+        let rgba = color_to_rgba8(clear_color);
+        for pixel in self.color.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&rgba);
+        }
+        self.depth.fill(1.0);
+    }
+
+    fn draw_triangles(&mut self, triangles: Vec<[ClipVertex; 3]>, shading: Shading) {
+        for triangle in triangles {
+            self.draw_triangle(triangle, shading);
+        }
+    }
+
+    fn draw_triangle(&mut self, triangle: [ClipVertex; 3], shading: Shading) {
+        let polygon = clip_polygon(&triangle);
+        if polygon.len() < 3 {
+            return;
+        }
+        let screen: Vec<ScreenVertex> = polygon
+            .iter()
+            .map(|vertex| self.to_screen(vertex))
+            .collect();
+        for index in 1..screen.len() - 1 {
+            self.rasterize(screen[0], screen[index], screen[index + 1], shading);
+        }
+    }
+
+    fn to_screen(&self, vertex: &ClipVertex) -> ScreenVertex {
+        let inv_w = 1.0 / vertex.position.w;
+        let ndc = vertex.position.truncate() * inv_w;
+        ScreenVertex {
+            x: (ndc.x * 0.5 + 0.5) * self.width as f32,
+            y: (0.5 - ndc.y * 0.5) * self.height as f32,
+            z: ndc.z,
+            inv_w,
+            color: vertex.color * inv_w,
+            uv: vertex.uv * inv_w,
+        }
+    }
+
+    fn rasterize(
+        &mut self,
+        v0: ScreenVertex,
+        mut v1: ScreenVertex,
+        mut v2: ScreenVertex,
+        shading: Shading,
+    ) {
+        let mut area = edge(&v0, &v1, v2.x, v2.y);
+        // Clockwise triangles on screen are front faces, matching the D3D11 default
+        // rasterizer state, so both backends cull the same faces.
+        if area < 0.0 && shading.material.two_sided {
+            std::mem::swap(&mut v1, &mut v2);
+            area = -area;
+        }
+        if area <= 0.0 {
+            return;
+        }
+        let min_x = v0.x.min(v1.x).min(v2.x).floor().max(0.0) as u32;
+        let min_y = v0.y.min(v1.y).min(v2.y).floor().max(0.0) as u32;
+        let max_x = (v0.x.max(v1.x).max(v2.x).ceil() as u32).min(self.width);
+        let max_y = (v0.y.max(v1.y).max(v2.y).ceil() as u32).min(self.height);
+        let blended = shading.material.is_blended();
+        let cutoff = shading.material.alpha_cutoff();
+        let top_left = [
+            is_top_left(&v1, &v2),
+            is_top_left(&v2, &v0),
+            is_top_left(&v0, &v1),
+        ];
+
+        for y in min_y..max_y {
+            let py = y as f32 + 0.5;
+            for x in min_x..max_x {
+                let px = x as f32 + 0.5;
+                let w0 = edge(&v1, &v2, px, py);
+                let w1 = edge(&v2, &v0, px, py);
+                let w2 = edge(&v0, &v1, px, py);
+                // Pixels exactly on an edge belong to it only if it's a top or left
+                // edge, so triangles sharing an edge don't both shade it.
+                let covered = [w0, w1, w2]
+                    .into_iter()
+                    .zip(top_left)
+                    .all(|(w, top_left)| w > 0.0 || (w == 0.0 && top_left));
+                if !covered {
+                    continue;
+                }
+                let (b0, b1, b2) = (w0 / area, w1 / area, w2 / area);
+                let z = b0 * v0.z + b1 * v1.z + b2 * v2.z;
+                let index = (y * self.width + x) as usize;
+                if !(0.0..=1.0).contains(&z) || (shading.depth_test && z >= self.depth[index]) {
+                    continue;
+                }
+                let inv_w = b0 * v0.inv_w + b1 * v1.inv_w + b2 * v2.inv_w;
+                let mut color = (v0.color * b0 + v1.color * b1 + v2.color * b2) / inv_w;
+                if let Some(texture) = shading.texture {
+                    let uv = (v0.uv * b0 + v1.uv * b1 + v2.uv * b2) / inv_w;
+                    color *= sample(texture, shading.material.sampler, uv);
+                }
+                if cutoff.is_some_and(|cutoff| color.w < cutoff) {
+                    continue;
+                }
+                let pixel = &mut self.color[index * 4..index * 4 + 4];
+                let rgb = if blended {
+                    let existing =
+                        Vec3::new(pixel[0] as f32, pixel[1] as f32, pixel[2] as f32) / 255.0;
+                    existing.lerp(color.truncate(), color.w.clamp(0.0, 1.0))
+                } else {
+                    self.depth[index] = z;
+                    color.truncate()
+                };
+                pixel.copy_from_slice(&color_to_rgba8(rgb.extend(1.0)));
+            }
+        }
+    }
+
+    fn draw_lines(&mut self, view_projection: Mat4, lines: &[DebugLine]) {
+        for line in lines {
+            let start = view_projection * line.start.extend(1.0);
+            let end = view_projection * line.end.extend(1.0);
+            let Some((start, end)) = clip_segment(start, end) else {
+                continue;
+            };
+            self.draw_segment(start, end, line.color);
+        }
+    }
+
+    fn draw_segment(&mut self, start: Vec4, end: Vec4, color: Vec4) {
+        let project = |position: Vec4| {
+            let ndc = position.truncate() / position.w;
+            (
+                (ndc.x * 0.5 + 0.5) * self.width as f32,
+                (0.5 - ndc.y * 0.5) * self.height as f32,
+            )
+        };
+        let (x0, y0) = project(start);
+        let (x1, y1) = project(end);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as u32;
+        let alpha = color.w.clamp(0.0, 1.0);
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            let x = (x0 + (x1 - x0) * t).floor();
+            let y = (y0 + (y1 - y0) * t).floor();
+            if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+                continue;
+            }
+            let index = (y as u32 * self.width + x as u32) as usize * 4;
+            let pixel = &mut self.color[index..index + 4];
+            let existing = Vec3::new(pixel[0] as f32, pixel[1] as f32, pixel[2] as f32) / 255.0;
+            let blended = existing.lerp(color.truncate(), alpha);
+            pixel.copy_from_slice(&color_to_rgba8(blended.extend(1.0)));
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderer {
+    fn new(target: RenderTarget) -> Result<Self, EngineError> {
+        match target {
+            RenderTarget::Window(window) => Self::with_window(window),
+            RenderTarget::Offscreen { width, height } => Self::offscreen(width, height),
+        }
+    }
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Software
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            presents_to_window: self.presenter.is_some(),
+            offscreen: true,
+            hardware_accelerated: false,
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        if width == self.target.width && height == self.target.height {
+            return;
+        }
+        // Keep drawing at the old size rather than panic on an absurd resize.
+        match Framebuffer::new(width, height) {
+            Ok(target) => self.target = target,
+            Err(err) => warn!("{err}"),
+        }
+    }
+
+    fn render(&mut self, frame: &RenderFrame) -> Result<(), EngineError> {
+        self.target.clear(frame.clear_color);
+        for item in sorted_draw_items(frame) {
+            let Some(mesh) = self.meshes.get(&item.mesh) else {
+                continue;
+            };
+            let shading = Shading {
+                material: &item.material,
+                texture: item
+                    .material
+                    .texture
+                    .and_then(|texture| self.textures.get(&texture)),
+                depth_test: true,
+            };
+            let triangles = mesh_triangles(
+                mesh,
+                frame.view_projection * item.world,
+                item.material.base_color,
+            );
+            self.target.draw_triangles(triangles, shading);
+        }
+        let screen_projection = Mat4::orthographic_rh(
+            0.0,
+            self.target.width as f32,
+            self.target.height as f32,
+            0.0,
+            -1.0,
+            1.0,
+        );
+        for space in [SpriteSpace::World, SpriteSpace::Screen] {
+            let (projection, depth_test) = match space {
+                SpriteSpace::World => (frame.view_projection, true),
+                SpriteSpace::Screen => (screen_projection, false),
+            };
+            for batch in frame.sprites.iter().filter(|batch| batch.space == space) {
+                let material = batch.material();
+                let shading = Shading {
+                    material: &material,
+                    texture: batch.texture.and_then(|texture| self.textures.get(&texture)),
+                    depth_test,
+                };
+                self.target
+                    .draw_triangles(sprite_triangles(batch, projection), shading);
+            }
+        }
+        self.target
+            .draw_lines(frame.view_projection, &frame.debug_lines);
+        self.present()
+    }
+
+    fn upload_mesh(&mut self, handle: MeshHandle, mesh: &Mesh) -> Result<(), EngineError> {
+        self.meshes.insert(handle, mesh.clone());
+        Ok(())
+    }
+
+    fn release_mesh(&mut self, handle: MeshHandle) {
+        self.meshes.remove(&handle);
+    }
+
+    fn upload_texture(&mut self, handle: TextureHandle, image: &Image) -> Result<(), EngineError> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(EngineError::Asset("texture has no pixels".to_string()));
+        }
+        self.textures.insert(handle, image.clone());
+        Ok(())
+    }
+
+    fn release_texture(&mut self, handle: TextureHandle) {
+        self.textures.remove(&handle);
+    }
+
+    fn capture(&mut self) -> Result<Image, EngineError> {
+        Image::from_rgba(
+            self.target.width,
+            self.target.height,
+            self.target.color.clone(),
+        )
+    }
+}
+
+fn mesh_triangles(mesh: &Mesh, mvp: Mat4, tint: Vec4) -> Vec<[ClipVertex; 3]> {
+    let vertices: Vec<ClipVertex> = mesh
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(index, position)| ClipVertex {
+            position: mvp * position.extend(1.0),
+            color: mesh.colors.get(index).copied().unwrap_or(Vec4::ONE) * tint,
+            uv: mesh.uvs.get(index).copied().unwrap_or(Vec2::ZERO),
+        })
+        .collect();
+    mesh.submesh_ranges()
+        .iter()
+        .flat_map(|submesh| {
+            let start = submesh.start as usize;
+            mesh.indices[start..start + submesh.count as usize].chunks_exact(3)
+        })
+        .map(|triangle| {
+            [
+                vertices[triangle[0] as usize],
+                vertices[triangle[1] as usize],
+                vertices[triangle[2] as usize],
+            ]
+        })
+        .collect()
+}
+
+fn sprite_triangles(batch: &SpriteBatch, projection: Mat4) -> Vec<[ClipVertex; 3]> {
+    let vertices: Vec<ClipVertex> = batch
+        .vertices
+        .iter()
+        .map(|vertex| ClipVertex {
+            position: projection * vertex.position.extend(1.0),
+            color: vertex.color,
+            uv: vertex.uv,
+        })
+        .collect();
+    batch
+        .indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            [
+                vertices[triangle[0] as usize],
+                vertices[triangle[1] as usize],
+                vertices[triangle[2] as usize],
+            ]
+        })
+        .collect()
+}
+
+fn edge(a: &ScreenVertex, b: &ScreenVertex, x: f32, y: f32) -> f32 {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
+
+// With y down and the interior on the positive side of `edge`, left edges run
+// upwards and top edges run left to right.
+fn is_top_left(a: &ScreenVertex, b: &ScreenVertex) -> bool {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+fn clip_polygon(triangle: &[ClipVertex; 3]) -> Vec<ClipVertex> {
+    let near = clip_against(triangle, |position| position.z);
+    clip_against(&near, |position| position.w - position.z)
+}
+
+fn clip_against(polygon: &[ClipVertex], distance: impl Fn(Vec4) -> f32) -> Vec<ClipVertex> {
+    let mut output = Vec::with_capacity(polygon.len() + 1);
+    for (index, current) in polygon.iter().enumerate() {
+        let next = &polygon[(index + 1) % polygon.len()];
+        let current_distance = distance(current.position);
+        let next_distance = distance(next.position);
+        if current_distance >= 0.0 {
+            output.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            output.push(ClipVertex {
+                position: current.position.lerp(next.position, t),
+                color: current.color.lerp(next.color, t),
+                uv: current.uv.lerp(next.uv, t),
+            });
+        }
+    }
+    output
+}
+
+fn clip_segment(mut start: Vec4, mut end: Vec4) -> Option<(Vec4, Vec4)> {
+    let planes: [fn(Vec4) -> f32; 6] = [
+        |p| p.z,
+        |p| p.w - p.z,
+        |p| p.w + p.x,
+        |p| p.w - p.x,
+        |p| p.w + p.y,
+        |p| p.w - p.y,
+    ];
+    for distance in planes {
+        let (d0, d1) = (distance(start), distance(end));
+        if d0 < 0.0 && d1 < 0.0 {
+            return None;
+        }
+        if d0 < 0.0 {
+            start = start.lerp(end, d0 / (d0 - d1));
+        } else if d1 < 0.0 {
+            end = start.lerp(end, d0 / (d0 - d1));
+        }
+    }
+    Some((start, end))
+}
+
+fn sample(image: &Image, sampler: Sampler, uv: Vec2) -> Vec4 {
+    let size = (image.width() as i64, image.height() as i64);
+    let texel = |x: i64, y: i64| {
+        let x = wrap(x, size.0, sampler.wrap);
+        let y = wrap(y, size.1, sampler.wrap);
+        Vec4::from_array(image.pixel(x, y).map(|channel| channel as f32)) / 255.0
+    };
+    let position = uv * Vec2::new(size.0 as f32, size.1 as f32);
+    match sampler.filter {
+        FilterMode::Nearest => texel(position.x.floor() as i64, position.y.floor() as i64),
+        FilterMode::Linear => {
+            let position = position - 0.5;
+            let base = position.floor();
+            let fraction = position - base;
+            let (x, y) = (base.x as i64, base.y as i64);
+            let top = texel(x, y).lerp(texel(x + 1, y), fraction.x);
+            let bottom = texel(x, y + 1).lerp(texel(x + 1, y + 1), fraction.x);
+            top.lerp(bottom, fraction.y)
+        }
+    }
+}
+
+fn wrap(coordinate: i64, size: i64, mode: WrapMode) -> u32 {
+    let wrapped = match mode {
+        WrapMode::Clamp => coordinate.clamp(0, size - 1),
+        WrapMode::Repeat => coordinate.rem_euclid(size),
+        WrapMode::Mirror => {
+            let period = coordinate.rem_euclid(size * 2);
+            if period < size {
+                period
+            } else {
+                size * 2 - 1 - period
+            }
+        }
+    };
+    wrapped as u32
+}
+
+fn color_to_rgba8(color: Vec4) -> [u8; 4] {
+    let scaled = (color.clamp(Vec4::ZERO, Vec4::ONE) * 255.0).round();
+    [scaled.x as u8, scaled.y as u8, scaled.z as u8, scaled.w as u8]
+}
@@ -0,0 +1,258 @@
+use crate::renderer::{AlphaMode, Material, Sampler, TextureHandle};
+use crate::scene::Scene;
+use glam::{Mat4, Vec2, Vec3, Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SpriteSpace {
+    // Pixels from the top-left corner of the render target, drawn over everything.
+    #[default]
+    Screen,
+    // Scene units in the sprite's local XY plane, depth tested against meshes.
+    World,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvRect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Default for UvRect {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl UvRect {
+    pub const FULL: Self = Self {
+        min: Vec2::ZERO,
+        max: Vec2::ONE,
+    };
+
+    pub fn from_pixels(x: u32, y: u32, width: u32, height: u32, atlas_size: (u32, u32)) -> Self {
+        let scale = Vec2::new(atlas_size.0 as f32, atlas_size.1 as f32).recip();
+        Self {
+            min: Vec2::new(x as f32, y as f32) * scale,
+            max: Vec2::new((x + width) as f32, (y + height) as f32) * scale,
+        }
+    }
+
+    pub fn grid_cell(column: u32, row: u32, columns: u32, rows: u32) -> Self {
+        let cell = Vec2::new(columns.max(1) as f32, rows.max(1) as f32).recip();
+        let min = Vec2::new(column as f32, row as f32) * cell;
+        Self {
+            min,
+            max: min + cell,
+        }
+    }
+
+    fn lerp(&self, t: Vec2) -> Vec2 {
+        self.min + (self.max - self.min) * t
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub texture: Option<TextureHandle>,
+    pub uv: UvRect,
+    pub size: Vec2,
+    // Normalized point the sprite rotates and is positioned around, (0, 0) being
+    // the top-left corner of the image.
+    pub pivot: Vec2,
+    pub tint: Vec4,
+    pub z_order: i32,
+    pub space: SpriteSpace,
+    pub sampler: Sampler,
+    pub visible: bool,
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            uv: UvRect::FULL,
+            size: Vec2::ONE,
+            pivot: Vec2::splat(0.5),
+            tint: Vec4::ONE,
+            z_order: 0,
+            space: SpriteSpace::Screen,
+            sampler: Sampler::default(),
+            visible: true,
+        }
+    }
+}
+
+impl Sprite {
+    pub fn new(texture: TextureHandle, size: Vec2) -> Self {
+        Self {
+            texture: Some(texture),
+            size,
+            ..Self::default()
+        }
+    }
+
+    pub fn solid(color: Vec4, size: Vec2) -> Self {
+        Self {
+            tint: color,
+            size,
+            ..Self::default()
+        }
+    }
+
+    pub fn in_world(mut self) -> Self {
+        self.space = SpriteSpace::World;
+        self
+    }
+
+    pub fn with_uv(mut self, uv: UvRect) -> Self {
+        self.uv = uv;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Vec4) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn with_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteVertex {
+    pub position: Vec3,
+    pub uv: Vec2,
+    pub color: Vec4,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteBatch {
+    pub space: SpriteSpace,
+    pub texture: Option<TextureHandle>,
+    pub sampler: Sampler,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl SpriteBatch {
+    pub fn sprite_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    pub(crate) fn material(&self) -> Material {
+        Material {
+            texture: self.texture,
+            sampler: self.sampler,
+            alpha_mode: AlphaMode::Blend,
+            two_sided: true,
+            ..Material::default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SpriteBatcher {
+    sprites: Vec<(Sprite, Mat4)>,
+}
+
+impl SpriteBatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, sprite: Sprite, transform: Mat4) {
+        if sprite.visible {
+            self.sprites.push((sprite, transform));
+        }
+    }
+
+    pub fn extend_from_scene(&mut self, scene: &Scene) {
+        for (entity, sprite) in scene.registry.iter::<Sprite>() {
+            self.push(*sprite, scene.registry.world_matrix(entity));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+
+    // World sprites come first, back to front within each z-order; screen sprites
+    // follow, grouped by texture within each z-order to keep batches large.
+    pub fn build(mut self, view_projection: Mat4) -> Vec<SpriteBatch> {
+        let depth = |transform: &Mat4| (view_projection * transform.w_axis).w;
+        let texture_key = |sprite: &Sprite| sprite.texture.map(|t| (t.index(), t.generation()));
+        self.sprites.sort_by(|(a, a_transform), (b, b_transform)| {
+            let a_world = a.space == SpriteSpace::World;
+            let b_world = b.space == SpriteSpace::World;
+            b_world
+                .cmp(&a_world)
+                .then(a.z_order.cmp(&b.z_order))
+                .then_with(|| {
+                    if a_world {
+                        depth(b_transform).total_cmp(&depth(a_transform))
+                    } else {
+                        texture_key(a).cmp(&texture_key(b))
+                    }
+                })
+        });
+
+        let mut batches: Vec<SpriteBatch> = Vec::new();
+        for (sprite, transform) in &self.sprites {
+            let continues = batches.last().is_some_and(|batch| {
+                batch.space == sprite.space
+                    && batch.texture == sprite.texture
+                    && batch.sampler == sprite.sampler
+            });
+            if !continues {
+                batches.push(SpriteBatch {
+                    space: sprite.space,
+                    texture: sprite.texture,
+                    sampler: sprite.sampler,
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+            if let Some(batch) = batches.last_mut() {
+                append_sprite(batch, sprite, transform);
+            }
+        }
+        batches
+    }
+}
+
+fn append_sprite(batch: &mut SpriteBatch, sprite: &Sprite, transform: &Mat4) {
+    let base = batch.vertices.len() as u32;
+    // Screen space runs y down like the image, world space runs y up.
+    let y_sign = match sprite.space {
+        SpriteSpace::Screen => 1.0,
+        SpriteSpace::World => -1.0,
+    };
+    for corner in [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y] {
+        let offset = (corner - sprite.pivot) * sprite.size;
+        batch.vertices.push(SpriteVertex {
+            position: transform.transform_point3(Vec3::new(offset.x, offset.y * y_sign, 0.0)),
+            uv: sprite.uv.lerp(corner),
+            color: sprite.tint,
+        });
+    }
+    batch
+        .indices
+        .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+}
+
+pub fn collect_sprite_batches(scene: &Scene, view_projection: Mat4) -> Vec<SpriteBatch> {
+    let mut batcher = SpriteBatcher::new();
+    batcher.extend_from_scene(scene);
+    batcher.build(view_projection)
+}
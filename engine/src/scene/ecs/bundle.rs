@@ -0,0 +1,30 @@
+use crate::scene::ecs::{Entity, Registry};
+
+pub trait Bundle: 'static {
+    fn insert_into(self, registry: &mut Registry, entity: Entity);
+}
+
+impl Bundle for () {
+    fn insert_into(self, _registry: &mut Registry, _entity: Entity) {}
+}
+
+macro_rules! impl_bundle_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: 'static),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert_into(self, registry: &mut Registry, entity: Entity) {
+                let ($($name,)+) = self;
+                $(registry.insert(entity, $name);)+
+            }
+        }
+    };
+}
+
+impl_bundle_tuple!(A);
+impl_bundle_tuple!(A, B);
+impl_bundle_tuple!(A, B, C);
+impl_bundle_tuple!(A, B, C, D);
+impl_bundle_tuple!(A, B, C, D, E);
+impl_bundle_tuple!(A, B, C, D, E, F);
+impl_bundle_tuple!(A, B, C, D, E, F, G);
+impl_bundle_tuple!(A, B, C, D, E, F, G, H);
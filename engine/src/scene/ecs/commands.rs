@@ -0,0 +1,56 @@
+use crate::scene::ecs::{Bundle, Entity, Registry};
+
+type Command = Box<dyn FnOnce(&mut Registry)>;
+
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, bundle: impl Bundle) {
+        self.push(move |registry| {
+            registry.spawn(bundle);
+        });
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.push(move |registry| {
+            registry.despawn(entity);
+        });
+    }
+
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) {
+        self.push(move |registry| {
+            registry.insert(entity, component);
+        });
+    }
+
+    pub fn remove<T: 'static>(&mut self, entity: Entity) {
+        self.push(move |registry| {
+            registry.remove::<T>(entity);
+        });
+    }
+
+    pub fn push(&mut self, command: impl FnOnce(&mut Registry) + 'static) {
+        self.queue.push(Box::new(command));
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn apply(&mut self, registry: &mut Registry) {
+        for command in self.queue.drain(..) {
+            command(registry);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+mod bundle;
+mod commands;
+mod query;
+mod storage;
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use storage::{ComponentStorage, RawSparseSet, SparseSet};
+
+pub use bundle::Bundle;
+pub use commands::Commands;
+pub use query::{Query, QueryIter, With, Without};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn index(self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    len: usize,
+    storages: HashMap<TypeId, Box<dyn ComponentStorage>>,
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("entities", &self.len)
+            .field("component_types", &self.storages.len())
+            .finish()
+    }
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.spawn_empty();
+        bundle.insert_into(self, entity);
+        entity
+    }
+
+    pub fn spawn_empty(&mut self) -> Entity {
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            self.alive[index as usize] = true;
+            return Entity {
+                index,
+                generation: self.generations[index as usize],
+            };
+        }
+        let index = self.generations.len() as u32;
+        self.generations.push(0);
+        self.alive.push(true);
+        Entity {
+            index,
+            generation: 0,
+        }
+    }
+
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        self.detach_hierarchy(entity);
+        for storage in self.storages.values_mut() {
+            storage.remove_entity(entity);
+        }
+        let index = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        self.len -= 1;
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        self.alive.get(index).copied().unwrap_or(false)
+            && self.generations[index] == entity.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(index, _)| Entity {
+                index: index as u32,
+                generation: self.generations[index],
+            })
+    }
+
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.storage_mut::<T>().insert(entity, component)
+    }
+
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        self.storage::<T>()?;
+        self.storage_mut::<T>().remove(entity)
+    }
+
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<&T> {
+        self.storage::<T>()?.get(entity)
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        let storage = self.storages.get_mut(&TypeId::of::<T>())?;
+        storage
+            .as_any_mut()
+            .downcast_mut::<SparseSet<T>>()?
+            .get_mut(entity)
+    }
+
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        self.storage::<T>()
+            .is_some_and(|storage| storage.contains(entity))
+    }
+
+    pub fn iter<T: 'static>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.storage::<T>().into_iter().flat_map(SparseSet::iter)
+    }
+
+    pub fn query<Q: Query>(&mut self) -> QueryIter<'_, Q> {
+        QueryIter::new(self)
+    }
+
+    pub fn query_one<Q: Query>(&mut self, entity: Entity) -> Option<Q::Item<'_>> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        query::check_access::<Q>();
+        let state = Q::prepare(self)?;
+        unsafe { Q::fetch(state, entity) }
+    }
+
+    pub(crate) fn raw_storage<T: 'static>(&mut self) -> Option<RawSparseSet<T>> {
+        let storage = self.storages.get_mut(&TypeId::of::<T>())?;
+        storage
+            .as_any_mut()
+            .downcast_mut::<SparseSet<T>>()
+            .map(SparseSet::raw)
+    }
+
+    fn storage<T: 'static>(&self) -> Option<&SparseSet<T>> {
+        self.storages
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref::<SparseSet<T>>()
+    }
+
+    fn storage_mut<T: 'static>(&mut self) -> &mut SparseSet<T> {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(SparseSet::<T>::default()))
+            .as_any_mut()
+            .downcast_mut::<SparseSet<T>>()
+            .expect("component storage registered under the wrong type")
+    }
+}
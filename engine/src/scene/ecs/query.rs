@@ -0,0 +1,248 @@
+use crate::scene::ecs::storage::RawSparseSet;
+use crate::scene::ecs::{Entity, Registry};
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub struct With<T>(PhantomData<T>);
+
+pub struct Without<T>(PhantomData<T>);
+
+pub trait Query: sealed::Sealed {
+    type Item<'a>;
+    #[doc(hidden)]
+    type State: Copy;
+
+    #[doc(hidden)]
+    fn access(access: &mut Vec<(TypeId, bool)>);
+
+    #[doc(hidden)]
+    fn prepare(registry: &mut Registry) -> Option<Self::State>;
+
+    #[doc(hidden)]
+    unsafe fn driver<'a>(state: &Self::State) -> Option<&'a [Entity]>;
+
+    #[doc(hidden)]
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>>;
+}
+
+impl<T: 'static> sealed::Sealed for &T {}
+
+impl<T: 'static> Query for &T {
+    type Item<'a> = &'a T;
+    type State = RawSparseSet<T>;
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), false));
+    }
+
+    fn prepare(registry: &mut Registry) -> Option<Self::State> {
+        registry.raw_storage::<T>()
+    }
+
+    unsafe fn driver<'a>(state: &Self::State) -> Option<&'a [Entity]> {
+        Some(unsafe { state.entities() })
+    }
+
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+        unsafe { state.get(entity) }
+    }
+}
+
+impl<T: 'static> sealed::Sealed for &mut T {}
+
+impl<T: 'static> Query for &mut T {
+    type Item<'a> = &'a mut T;
+    type State = RawSparseSet<T>;
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        access.push((TypeId::of::<T>(), true));
+    }
+
+    fn prepare(registry: &mut Registry) -> Option<Self::State> {
+        registry.raw_storage::<T>()
+    }
+
+    unsafe fn driver<'a>(state: &Self::State) -> Option<&'a [Entity]> {
+        Some(unsafe { state.entities() })
+    }
+
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+        unsafe { state.get_mut(entity) }
+    }
+}
+
+impl<Q: Query> sealed::Sealed for Option<Q> {}
+
+impl<Q: Query> Query for Option<Q> {
+    type Item<'a> = Option<Q::Item<'a>>;
+    type State = Option<Q::State>;
+
+    fn access(access: &mut Vec<(TypeId, bool)>) {
+        Q::access(access);
+    }
+
+    fn prepare(registry: &mut Registry) -> Option<Self::State> {
+        Some(Q::prepare(registry))
+    }
+
+    unsafe fn driver<'a>(_state: &Self::State) -> Option<&'a [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+        Some(state.and_then(|state| unsafe { Q::fetch(state, entity) }))
+    }
+}
+
+impl<T: 'static> sealed::Sealed for With<T> {}
+
+impl<T: 'static> Query for With<T> {
+    type Item<'a> = ();
+    type State = RawSparseSet<T>;
+
+    fn access(_access: &mut Vec<(TypeId, bool)>) {}
+
+    fn prepare(registry: &mut Registry) -> Option<Self::State> {
+        registry.raw_storage::<T>()
+    }
+
+    unsafe fn driver<'a>(state: &Self::State) -> Option<&'a [Entity]> {
+        Some(unsafe { state.entities() })
+    }
+
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+        unsafe { state.contains(entity) }.then_some(())
+    }
+}
+
+impl<T: 'static> sealed::Sealed for Without<T> {}
+
+impl<T: 'static> Query for Without<T> {
+    type Item<'a> = ();
+    type State = Option<RawSparseSet<T>>;
+
+    fn access(_access: &mut Vec<(TypeId, bool)>) {}
+
+    fn prepare(registry: &mut Registry) -> Option<Self::State> {
+        Some(registry.raw_storage::<T>())
+    }
+
+    unsafe fn driver<'a>(_state: &Self::State) -> Option<&'a [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+        match state {
+            Some(storage) if unsafe { storage.contains(entity) } => None,
+            _ => Some(()),
+        }
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Query),+> sealed::Sealed for ($($name,)+) {}
+
+        impl<$($name: Query),+> Query for ($($name,)+) {
+            type Item<'a> = ($($name::Item<'a>,)+);
+            type State = ($($name::State,)+);
+
+            fn access(access: &mut Vec<(TypeId, bool)>) {
+                $($name::access(access);)+
+            }
+
+            fn prepare(registry: &mut Registry) -> Option<Self::State> {
+                Some(($($name::prepare(registry)?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn driver<'a>(state: &Self::State) -> Option<&'a [Entity]> {
+                let ($($name,)+) = state;
+                let mut best: Option<&'a [Entity]> = None;
+                $(
+                    if let Some(candidate) = unsafe { $name::driver($name) } {
+                        if best.is_none_or(|best| candidate.len() < best.len()) {
+                            best = Some(candidate);
+                        }
+                    }
+                )+
+                best
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn fetch<'a>(state: Self::State, entity: Entity) -> Option<Self::Item<'a>> {
+                let ($($name,)+) = state;
+                Some(($(unsafe { $name::fetch($name, entity) }?,)+))
+            }
+        }
+    };
+}
+
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+impl_query_tuple!(A, B, C, D, E);
+impl_query_tuple!(A, B, C, D, E, F);
+
+pub struct QueryIter<'a, Q: Query> {
+    state: Option<Q::State>,
+    entities: Vec<Entity>,
+    next: usize,
+    _registry: PhantomData<&'a mut Registry>,
+}
+
+impl<'a, Q: Query> QueryIter<'a, Q> {
+    pub(crate) fn new(registry: &'a mut Registry) -> Self {
+        check_access::<Q>();
+        let state = Q::prepare(registry);
+        let entities = match state.as_ref() {
+            Some(state) => match unsafe { Q::driver(state) } {
+                Some(entities) => entities.to_vec(),
+                None => registry.entities().collect(),
+            },
+            None => Vec::new(),
+        };
+        Self {
+            state,
+            entities,
+            next: 0,
+            _registry: PhantomData,
+        }
+    }
+}
+
+impl<'a, Q: Query> Iterator for QueryIter<'a, Q> {
+    type Item = (Entity, Q::Item<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state?;
+        while let Some(&entity) = self.entities.get(self.next) {
+            self.next += 1;
+            // Each entity is visited once, so the references handed out never alias.
+            if let Some(item) = unsafe { Q::fetch(state, entity) } {
+                return Some((entity, item));
+            }
+        }
+        None
+    }
+}
+
+pub(crate) fn check_access<Q: Query>() {
+    let mut access = Vec::new();
+    Q::access(&mut access);
+    for (index, (type_id, mutable)) in access.iter().enumerate() {
+        for (other_id, other_mutable) in &access[index + 1..] {
+            if type_id == other_id && (*mutable || *other_mutable) {
+                panic!(
+                    "query {} borrows the same component mutably more than once",
+                    std::any::type_name::<Q>()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::scene::ecs::Entity;
+use std::any::Any;
+
+const EMPTY: u32 = u32::MAX;
+
+pub(crate) trait ComponentStorage: Any {
+    fn remove_entity(&mut self, entity: Entity);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub struct SparseSet<T> {
+    sparse: Vec<u32>,
+    entities: Vec<Entity>,
+    values: Vec<T>,
+}
+
+impl<T> Default for SparseSet<T> {
+    fn default() -> Self {
+        Self {
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T> SparseSet<T> {
+    pub fn insert(&mut self, entity: Entity, value: T) -> Option<T> {
+        let index = entity.index() as usize;
+        if index >= self.sparse.len() {
+            self.sparse.resize(index + 1, EMPTY);
+        }
+        match self.dense_index(entity) {
+            Some(dense) => Some(std::mem::replace(&mut self.values[dense], value)),
+            None => {
+                if self.sparse[index] != EMPTY {
+                    // A stale component from a previous generation must never survive.
+                    self.remove_at(self.sparse[index] as usize);
+                }
+                self.sparse[index] = self.entities.len() as u32;
+                self.entities.push(entity);
+                self.values.push(value);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let dense = self.dense_index(entity)?;
+        Some(self.remove_at(dense))
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.dense_index(entity).map(|dense| &self.values[dense])
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.dense_index(entity)
+            .map(|dense| &mut self.values[dense])
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.dense_index(entity).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.entities.iter().copied().zip(self.values.iter())
+    }
+
+    pub(crate) fn raw(&mut self) -> RawSparseSet<T> {
+        RawSparseSet {
+            sparse: std::ptr::slice_from_raw_parts(self.sparse.as_ptr(), self.sparse.len()),
+            entities: std::ptr::slice_from_raw_parts(self.entities.as_ptr(), self.entities.len()),
+            values: self.values.as_mut_ptr(),
+        }
+    }
+
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        let dense = *self.sparse.get(entity.index() as usize)?;
+        if dense == EMPTY || self.entities[dense as usize] != entity {
+            return None;
+        }
+        Some(dense as usize)
+    }
+
+    fn remove_at(&mut self, dense: usize) -> T {
+        let removed = self.entities[dense];
+        self.sparse[removed.index() as usize] = EMPTY;
+        let value = self.values.swap_remove(dense);
+        self.entities.swap_remove(dense);
+        if let Some(moved) = self.entities.get(dense) {
+            self.sparse[moved.index() as usize] = dense as u32;
+        }
+        value
+    }
+}
+
+// Raw pointers into a storage's buffers, taken once when a query starts. Items
+// are fetched through `values` directly, so handing out `&mut T` for one entity
+// never reborrows the storage or the items already handed out. The index
+// buffers are only ever read while a query runs.
+pub struct RawSparseSet<T> {
+    sparse: *const [u32],
+    entities: *const [Entity],
+    values: *mut T,
+}
+
+impl<T> Clone for RawSparseSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RawSparseSet<T> {}
+
+impl<T> RawSparseSet<T> {
+    pub(crate) unsafe fn entities<'a>(self) -> &'a [Entity] {
+        unsafe { &*self.entities }
+    }
+
+    pub(crate) unsafe fn contains(self, entity: Entity) -> bool {
+        unsafe { self.dense_index(entity) }.is_some()
+    }
+
+    pub(crate) unsafe fn get<'a>(self, entity: Entity) -> Option<&'a T> {
+        let dense = unsafe { self.dense_index(entity) }?;
+        Some(unsafe { &*self.values.add(dense) })
+    }
+
+    pub(crate) unsafe fn get_mut<'a>(self, entity: Entity) -> Option<&'a mut T> {
+        let dense = unsafe { self.dense_index(entity) }?;
+        Some(unsafe { &mut *self.values.add(dense) })
+    }
+
+    unsafe fn dense_index(self, entity: Entity) -> Option<usize> {
+        let dense = *unsafe { &*self.sparse }.get(entity.index() as usize)?;
+        if dense == EMPTY || unsafe { self.entities() }[dense as usize] != entity {
+            return None;
+        }
+        Some(dense as usize)
+    }
+}
+
+impl<T: 'static> ComponentStorage for SparseSet<T> {
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(entity);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,216 @@
+use crate::error::EngineError;
+use crate::scene::ecs::{Entity, Registry};
+use crate::scene::Transform;
+use glam::Mat4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(Entity);
+
+impl Parent {
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Children(Vec<Entity>);
+
+impl Children {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTransform(Mat4);
+
+impl GlobalTransform {
+    pub fn matrix(&self) -> Mat4 {
+        self.0
+    }
+
+    pub fn to_transform(&self) -> Transform {
+        Transform::from_matrix(self.0)
+    }
+}
+
+// Inputs the cached GlobalTransform was computed from.
+struct Propagated {
+    local: Transform,
+    parent: Mat4,
+}
+
+struct TransformDirty;
+
+impl Registry {
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        self.get::<Parent>(entity).map(Parent::get)
+    }
+
+    pub fn set_parent(&mut self, child: Entity, parent: Option<Entity>) -> Result<(), EngineError> {
+        if !self.is_alive(child) {
+            return Err(EngineError::Scene(format!("entity {child:?} is not alive")));
+        }
+        if let Some(parent) = parent {
+            if !self.is_alive(parent) {
+                return Err(EngineError::Scene(format!("parent {parent:?} is not alive")));
+            }
+            if parent == child || self.is_ancestor_of(child, parent) {
+                return Err(EngineError::Scene(format!(
+                    "parenting {child:?} under {parent:?} would create a cycle"
+                )));
+            }
+        }
+
+        if let Some(Parent(old_parent)) = self.remove::<Parent>(child) {
+            if let Some(children) = self.get_mut::<Children>(old_parent) {
+                children.0.retain(|&entity| entity != child);
+            }
+        }
+        if let Some(parent) = parent {
+            self.insert(child, Parent(parent));
+            match self.get_mut::<Children>(parent) {
+                Some(children) => children.0.push(child),
+                None => {
+                    self.insert(parent, Children(vec![child]));
+                }
+            }
+        }
+        self.insert(child, TransformDirty);
+        Ok(())
+    }
+
+    pub fn set_parent_keep_world(
+        &mut self,
+        child: Entity,
+        parent: Option<Entity>,
+    ) -> Result<(), EngineError> {
+        let child_world = self.world_matrix(child);
+        let parent_world = parent.map_or(Mat4::IDENTITY, |parent| self.world_matrix(parent));
+        self.set_parent(child, parent)?;
+        let local = Transform::from_matrix(parent_world.inverse() * child_world);
+        self.insert(child, local);
+        Ok(())
+    }
+
+    pub fn world_matrix(&self, entity: Entity) -> Mat4 {
+        let mut matrix = Mat4::IDENTITY;
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            // An entity without a Transform ends the chain, as in propagation.
+            let Some(local) = self.get::<Transform>(entity) else {
+                break;
+            };
+            matrix = local.to_matrix() * matrix;
+            current = self.parent(entity);
+        }
+        matrix
+    }
+
+    pub fn despawn_recursive(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let _ = self.set_parent(entity, None);
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            if let Some(children) = self.remove::<Children>(entity) {
+                stack.extend(children.0);
+            }
+            self.despawn(entity);
+        }
+        true
+    }
+
+    // Called by despawn so no live entity keeps a link to a dead one: the
+    // entity leaves its parent's Children and its children become roots.
+    pub(crate) fn detach_hierarchy(&mut self, entity: Entity) {
+        if let Some(Parent(parent)) = self.get::<Parent>(entity).copied() {
+            if let Some(children) = self.get_mut::<Children>(parent) {
+                children.0.retain(|&child| child != entity);
+            }
+        }
+        if let Some(children) = self.remove::<Children>(entity) {
+            for child in children.0 {
+                self.remove::<Parent>(child);
+                self.insert(child, TransformDirty);
+            }
+        }
+    }
+
+    // Propagation starts from entities whose parent is missing, dead or has no
+    // Transform of its own.
+    fn is_transform_root(&self, entity: Entity) -> bool {
+        self.parent(entity).is_none_or(|parent| !self.has::<Transform>(parent))
+    }
+
+    fn is_ancestor_of(&self, ancestor: Entity, entity: Entity) -> bool {
+        let mut current = self.parent(entity);
+        while let Some(entity) = current {
+            if entity == ancestor {
+                return true;
+            }
+            current = self.parent(entity);
+        }
+        false
+    }
+}
+
+pub fn propagate_transforms(registry: &mut Registry) {
+    let mut roots: Vec<Entity> = registry
+        .query::<&Transform>()
+        .map(|(entity, _)| entity)
+        .collect();
+    roots.retain(|&entity| registry.is_transform_root(entity));
+    let mut stack: Vec<(Entity, Mat4, bool)> = roots
+        .into_iter()
+        .map(|root| (root, Mat4::IDENTITY, false))
+        .collect();
+
+    while let Some((entity, parent_matrix, parent_changed)) = stack.pop() {
+        if !registry.is_alive(entity) {
+            continue;
+        }
+        // Children of an entity without a Transform are roots of their own.
+        let Some(local) = registry.get::<Transform>(entity).copied() else {
+            registry.remove::<GlobalTransform>(entity);
+            registry.remove::<Propagated>(entity);
+            continue;
+        };
+        let changed = parent_changed
+            || registry.remove::<TransformDirty>(entity).is_some()
+            || registry
+                .get::<Propagated>(entity)
+                .is_none_or(|propagated| {
+                    propagated.local != local || propagated.parent != parent_matrix
+                });
+
+        let matrix = match registry.get::<GlobalTransform>(entity) {
+            Some(global) if !changed => global.0,
+            _ => {
+                let matrix = parent_matrix * local.to_matrix();
+                registry.insert(entity, GlobalTransform(matrix));
+                registry.insert(
+                    entity,
+                    Propagated {
+                        local,
+                        parent: parent_matrix,
+                    },
+                );
+                matrix
+            }
+        };
+
+        if let Some(children) = registry.get::<Children>(entity) {
+            stack.extend(children.iter().map(|child| (child, matrix, changed)));
+        }
+    }
+}
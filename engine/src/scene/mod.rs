@@ -1,26 +1,52 @@
+pub mod ecs;
+mod hierarchy;
 mod world;
 
-use glam::Vec4;
+use glam::{Mat4, Quat, Vec3, Vec4};
 
+pub use ecs::{Commands, Entity, Registry};
+pub use hierarchy::{propagate_transforms, Children, GlobalTransform, Parent};
 pub use world::{Camera, Scene, SceneEnvironment};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transform {
-    pub position: glam::Vec3,
-    pub rotation: glam::Quat,
-    pub scale: glam::Vec3,
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
 }
 
 impl Default for Transform {
     fn default() -> Self {
         Self {
-            position: glam::Vec3::ZERO,
-            rotation: glam::Quat::IDENTITY,
-            scale: glam::Vec3::ONE,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
         }
     }
 }
 
+impl Transform {
+    pub fn from_position(position: Vec3) -> Self {
+        Self {
+            position,
+            ..Self::default()
+        }
+    }
+
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        let (scale, rotation, position) = matrix.to_scale_rotation_translation();
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.position)
+    }
+}
+
 pub fn default_clear_color() -> Vec4 {
     Vec4::new(0.08, 0.09, 0.14, 1.0)
 }
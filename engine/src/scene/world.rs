@@ -1,4 +1,7 @@
-use crate::scene::default_clear_color;
+use crate::assets::Assets;
+use crate::renderer::{Image, Material, Mesh};
+use crate::text::Font;
+use crate::scene::{default_clear_color, propagate_transforms, Registry};
 use glam::{Mat4, Vec3, Vec4};
 
 #[derive(Debug, Clone)]
@@ -47,19 +50,17 @@ impl Camera {
     }
 }
 
-#[derive(Debug, Clone)]
+// Not Clone: the registry stores type-erased components, which can't be cloned
+// without requiring every component type to be Clone.
+#[derive(Debug, Default)]
 pub struct Scene {
     pub environment: SceneEnvironment,
     pub main_camera: Camera,
-}
-
-impl Default for Scene {
-    fn default() -> Self {
-        Self {
-            environment: SceneEnvironment::default(),
-            main_camera: Camera::default(),
-        }
-    }
+    pub registry: Registry,
+    pub meshes: Assets<Mesh>,
+    pub materials: Assets<Material>,
+    pub textures: Assets<Image>,
+    pub fonts: Assets<Font>,
 }
 
 impl Scene {
@@ -67,5 +68,6 @@ impl Scene {
         let t = (delta_seconds * 0.2).min(1.0);
         let shift = Vec4::new(t * 0.1, 0.0, 0.0, 0.0);
         self.environment.clear_color = (self.environment.clear_color + shift).clamp(Vec4::ZERO, Vec4::ONE);
+        propagate_transforms(&mut self.registry);
     }
 }
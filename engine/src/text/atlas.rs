@@ -0,0 +1,247 @@
+use crate::renderer::{Image, UvRect};
+use crate::text::{Font, FontHandle};
+use ab_glyph::{point, Font as _, GlyphId, PxScale};
+use glam::Vec2;
+use std::collections::HashMap;
+use tracing::warn;
+
+const ATLAS_WIDTH: u32 = 1024;
+const INITIAL_HEIGHT: u32 = 256;
+const MAX_HEIGHT: u32 = 4096;
+const PADDING: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct GlyphKey {
+    font: FontHandle,
+    glyph: GlyphId,
+    size: u32,
+    outline: u32,
+}
+
+impl GlyphKey {
+    pub(crate) fn new(font: FontHandle, glyph: GlyphId, size: f32, outline: f32) -> Self {
+        Self {
+            font,
+            glyph,
+            size: size.to_bits(),
+            outline: outline.to_bits(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GlyphEntry {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    // From the pen position on the baseline to the top-left of the bitmap.
+    pub offset: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AtlasFull;
+
+// Shelf-packed coverage atlas: white texels with glyph coverage in alpha, so a
+// sprite tint picks the text color.
+#[derive(Debug)]
+pub(crate) struct GlyphAtlas {
+    image: Image,
+    entries: HashMap<GlyphKey, Option<GlyphEntry>>,
+    cursor: (u32, u32),
+    shelf_height: u32,
+    dirty: bool,
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        Self {
+            image: blank_image(INITIAL_HEIGHT),
+            entries: HashMap::new(),
+            cursor: (PADDING, PADDING),
+            shelf_height: 0,
+            dirty: true,
+        }
+    }
+}
+
+impl GlyphAtlas {
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn get(&self, key: &GlyphKey) -> Option<GlyphEntry> {
+        self.entries.get(key).copied().flatten()
+    }
+
+    pub fn uv(&self, entry: &GlyphEntry) -> UvRect {
+        UvRect::from_pixels(
+            entry.x,
+            entry.y,
+            entry.width,
+            entry.height,
+            (self.image.width(), self.image.height()),
+        )
+    }
+
+    pub fn rasterize(
+        &mut self,
+        key: GlyphKey,
+        font: &Font,
+    ) -> Result<Option<GlyphEntry>, AtlasFull> {
+        if let Some(entry) = self.entries.get(&key) {
+            return Ok(*entry);
+        }
+        let Some(bitmap) = rasterize_glyph(font, key) else {
+            self.entries.insert(key, None);
+            return Ok(None);
+        };
+        // Clearing the atlas can't make room for these, so they are dropped
+        // once rather than forcing a rebuild every frame.
+        if bitmap.width + 2 * PADDING > ATLAS_WIDTH || bitmap.height + 2 * PADDING > MAX_HEIGHT {
+            warn!(
+                "glyph {:?} at {}px is {}x{}, larger than the glyph atlas; skipping it",
+                key.glyph,
+                f32::from_bits(key.size),
+                bitmap.width,
+                bitmap.height
+            );
+            self.entries.insert(key, None);
+            return Ok(None);
+        }
+        let (x, y) = self.allocate(bitmap.width, bitmap.height)?;
+        for row in 0..bitmap.height {
+            for column in 0..bitmap.width {
+                let coverage = bitmap.coverage[(row * bitmap.width + column) as usize];
+                let alpha = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                self.image
+                    .set_pixel(x + column, y + row, [255, 255, 255, alpha]);
+            }
+        }
+        let entry = GlyphEntry {
+            x,
+            y,
+            width: bitmap.width,
+            height: bitmap.height,
+            offset: bitmap.offset,
+        };
+        self.entries.insert(key, Some(entry));
+        self.dirty = true;
+        Ok(Some(entry))
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Result<(u32, u32), AtlasFull> {
+        if self.cursor.0 + width + PADDING > ATLAS_WIDTH {
+            self.cursor = (PADDING, self.cursor.1 + self.shelf_height + PADDING);
+            self.shelf_height = 0;
+        }
+        while self.cursor.1 + height + PADDING > self.image.height() {
+            let grown = self.image.height() * 2;
+            if grown > MAX_HEIGHT {
+                return Err(AtlasFull);
+            }
+            let mut image = blank_image(grown);
+            let used = self.image.pixels().len();
+            image.pixels_mut()[..used].copy_from_slice(self.image.pixels());
+            self.image = image;
+        }
+        let position = self.cursor;
+        self.cursor.0 += width + PADDING;
+        self.shelf_height = self.shelf_height.max(height);
+        Ok(position)
+    }
+}
+
+struct GlyphBitmap {
+    coverage: Vec<f32>,
+    width: u32,
+    height: u32,
+    offset: Vec2,
+}
+
+fn blank_image(height: u32) -> Image {
+    // Transparent white keeps filtered glyph edges from darkening.
+    let pixels = [255, 255, 255, 0].repeat((ATLAS_WIDTH * height) as usize);
+    Image::from_rgba(ATLAS_WIDTH, height, pixels).expect("atlas dimensions match")
+}
+
+fn rasterize_glyph(font: &Font, key: GlyphKey) -> Option<GlyphBitmap> {
+    let size = f32::from_bits(key.size);
+    let glyph = key
+        .glyph
+        .with_scale_and_position(PxScale::from(size), point(0.0, 0.0));
+    let outlined = font.arc().outline_glyph(glyph)?;
+    let bounds = outlined.px_bounds();
+    let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    // Huge font sizes give bounds whose area doesn't fit in u32.
+    let mut coverage = vec![0.0; width as usize * height as usize];
+    outlined.draw(|x, y, value| {
+        if x < width && y < height {
+            coverage[y as usize * width as usize + x as usize] = value.min(1.0);
+        }
+    });
+    let bitmap = GlyphBitmap {
+        coverage,
+        width,
+        height,
+        offset: Vec2::new(bounds.min.x, bounds.min.y),
+    };
+    let outline = f32::from_bits(key.outline);
+    Some(if outline > 0.0 {
+        dilate(&bitmap, outline)
+    } else {
+        bitmap
+    })
+}
+
+// Grows the coverage by `radius` pixels with a round brush that is solid out to
+// the radius and fades over one more pixel, giving the stroke behind outlined text.
+fn dilate(bitmap: &GlyphBitmap, radius: f32) -> GlyphBitmap {
+    // The brush reaches one pixel past the radius.
+    let pad = radius.ceil() as i32 + 1;
+    let width = bitmap.width as i32 + 2 * pad;
+    let height = bitmap.height as i32 + 2 * pad;
+    let mut brush = Vec::new();
+    for dy in -pad..=pad {
+        for dx in -pad..=pad {
+            let weight = (radius + 1.0 - ((dx * dx + dy * dy) as f32).sqrt()).clamp(0.0, 1.0);
+            if weight > 0.0 {
+                brush.push((dx, dy, weight));
+            }
+        }
+    }
+    let mut coverage = vec![0.0f32; width as usize * height as usize];
+    for y in 0..bitmap.height as i32 {
+        for x in 0..bitmap.width as i32 {
+            let value = bitmap.coverage[y as usize * bitmap.width as usize + x as usize];
+            if value <= 0.0 {
+                continue;
+            }
+            for &(dx, dy, weight) in &brush {
+                let index = (y + pad + dy) as usize * width as usize + (x + pad + dx) as usize;
+                coverage[index] = coverage[index].max(value * weight);
+            }
+        }
+    }
+    GlyphBitmap {
+        coverage,
+        width: width as u32,
+        height: height as u32,
+        offset: bitmap.offset - Vec2::splat(pad as f32),
+    }
+}
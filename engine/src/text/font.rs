@@ -0,0 +1,70 @@
+use crate::assets::Handle;
+use crate::error::EngineError;
+use ab_glyph::{Font as _, FontArc, GlyphId, PxScale, ScaleFont};
+use std::path::Path;
+
+pub type FontHandle = Handle<Font>;
+
+#[derive(Debug, Clone)]
+pub struct Font {
+    inner: FontArc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl LineMetrics {
+    pub fn height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+}
+
+impl Font {
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, EngineError> {
+        let inner = FontArc::try_from_vec(bytes)
+            .map_err(|err| EngineError::Asset(format!("font parse: {err}")))?;
+        Ok(Self { inner })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|err| EngineError::Io(format!("{}: {err}", path.display())))?;
+        Self::from_bytes(bytes)
+    }
+
+    pub fn line_metrics(&self, size: f32) -> LineMetrics {
+        let scaled = self.inner.as_scaled(PxScale::from(size));
+        LineMetrics {
+            ascent: scaled.ascent(),
+            descent: scaled.descent(),
+            line_gap: scaled.line_gap(),
+        }
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.inner.glyph_id(c).0 != 0
+    }
+
+    pub(crate) fn glyph_id(&self, c: char) -> GlyphId {
+        self.inner.glyph_id(c)
+    }
+
+    pub(crate) fn advance(&self, glyph: GlyphId, size: f32) -> f32 {
+        self.inner.as_scaled(PxScale::from(size)).h_advance(glyph)
+    }
+
+    pub(crate) fn kern(&self, first: GlyphId, second: GlyphId, size: f32) -> f32 {
+        self.inner
+            .as_scaled(PxScale::from(size))
+            .kern(first, second)
+    }
+
+    pub(crate) fn arc(&self) -> &FontArc {
+        &self.inner
+    }
+}
@@ -0,0 +1,183 @@
+use crate::text::Font;
+use ab_glyph::GlyphId;
+use glam::{Vec2, Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outline {
+    pub width: f32,
+    pub color: Vec4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub size: f32,
+    pub color: Vec4,
+    pub align: TextAlign,
+    pub max_width: Option<f32>,
+    pub line_spacing: f32,
+    pub outline: Option<Outline>,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            size: 32.0,
+            color: Vec4::ONE,
+            align: TextAlign::Left,
+            max_width: None,
+            line_spacing: 1.0,
+            outline: None,
+        }
+    }
+}
+
+impl TextStyle {
+    pub fn new(size: f32) -> Self {
+        Self {
+            size,
+            ..Self::default()
+        }
+    }
+
+    // White centered text with a black border, the classic caption look.
+    pub fn meme(size: f32) -> Self {
+        Self {
+            size,
+            align: TextAlign::Center,
+            outline: Some(Outline {
+                width: (size / 16.0).max(1.0),
+                color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+            }),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn with_outline(mut self, width: f32, color: Vec4) -> Self {
+        self.outline = Some(Outline { width, color });
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaidOutGlyph {
+    pub character: char,
+    // Pen position on the baseline, in pixels from the top-left of the block.
+    pub position: Vec2,
+    pub(crate) id: GlyphId,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextLayout {
+    pub glyphs: Vec<LaidOutGlyph>,
+    pub line_widths: Vec<f32>,
+    pub size: Vec2,
+}
+
+impl TextLayout {
+    pub fn line_count(&self) -> usize {
+        self.line_widths.len()
+    }
+}
+
+pub fn layout_text(font: &Font, content: &str, style: &TextStyle) -> TextLayout {
+    let lines = wrap_lines(font, content, style);
+    let metrics = font.line_metrics(style.size);
+    let line_height = metrics.height() * style.line_spacing;
+    let line_widths: Vec<f32> = lines
+        .iter()
+        .map(|line| line_width(font, line, style.size))
+        .collect();
+    let widest = line_widths.iter().copied().fold(0.0, f32::max);
+    let block_width = style.max_width.map_or(widest, |max| max.max(widest));
+
+    let mut glyphs = Vec::new();
+    for (index, (line, width)) in lines.iter().zip(&line_widths).enumerate() {
+        let mut x = match style.align {
+            TextAlign::Left => 0.0,
+            TextAlign::Center => (block_width - width) * 0.5,
+            TextAlign::Right => block_width - width,
+        };
+        let baseline = metrics.ascent + index as f32 * line_height;
+        let mut previous = None;
+        for character in line.chars() {
+            let id = font.glyph_id(character);
+            if let Some(previous) = previous {
+                x += font.kern(previous, id, style.size);
+            }
+            glyphs.push(LaidOutGlyph {
+                character,
+                position: Vec2::new(x, baseline),
+                id,
+            });
+            x += font.advance(id, style.size);
+            previous = Some(id);
+        }
+    }
+    let height = metrics.ascent - metrics.descent + (lines.len() - 1) as f32 * line_height;
+    TextLayout {
+        glyphs,
+        line_widths,
+        size: Vec2::new(block_width, height),
+    }
+}
+
+fn wrap_lines(font: &Font, content: &str, style: &TextStyle) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in content.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.trim_end_matches('\r').split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            let overflows = style
+                .max_width
+                .is_some_and(|max| line_width(font, &candidate, style.size) > max);
+            if overflows && !line.is_empty() {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn line_width(font: &Font, line: &str, size: f32) -> f32 {
+    let mut width = 0.0;
+    let mut previous = None;
+    for character in line.chars() {
+        let id = font.glyph_id(character);
+        if let Some(previous) = previous {
+            width += font.kern(previous, id, size);
+        }
+        width += font.advance(id, size);
+        previous = Some(id);
+    }
+    width
+}
@@ -0,0 +1,204 @@
+mod atlas;
+mod font;
+mod layout;
+
+use crate::assets::Assets;
+use crate::renderer::{Image, Sampler, Sprite, SpriteBatcher, SpriteSpace, TextureHandle};
+use crate::scene::Scene;
+use atlas::{GlyphAtlas, GlyphKey};
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use tracing::warn;
+
+pub use font::{Font, FontHandle, LineMetrics};
+pub use layout::{layout_text, LaidOutGlyph, Outline, TextAlign, TextLayout, TextStyle};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    pub content: String,
+    pub font: FontHandle,
+    pub style: TextStyle,
+    pub space: SpriteSpace,
+    pub pivot: Vec2,
+    pub z_order: i32,
+    // Only used in world space, where layout pixels are scaled down to scene units.
+    pub pixels_per_unit: f32,
+    pub visible: bool,
+}
+
+impl Text {
+    pub fn new(content: impl Into<String>, font: FontHandle, style: TextStyle) -> Self {
+        Self {
+            content: content.into(),
+            font,
+            style,
+            space: SpriteSpace::Screen,
+            pivot: Vec2::splat(0.5),
+            z_order: 0,
+            pixels_per_unit: 100.0,
+            visible: true,
+        }
+    }
+
+    pub fn in_world(mut self, pixels_per_unit: f32) -> Self {
+        self.space = SpriteSpace::World;
+        self.pixels_per_unit = pixels_per_unit;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn with_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+}
+
+struct PreparedText<'a> {
+    text: &'a Text,
+    transform: Mat4,
+    font: &'a Font,
+    layout: TextLayout,
+}
+
+// Lays text out into glyph sprites that all sample one dynamic atlas texture,
+// so captions batch together with the rest of the sprite layer.
+#[derive(Debug, Default)]
+pub struct TextRenderer {
+    atlas: GlyphAtlas,
+    texture: Option<TextureHandle>,
+}
+
+impl TextRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn atlas_texture(&self) -> Option<TextureHandle> {
+        self.texture
+    }
+
+    pub fn cached_glyphs(&self) -> usize {
+        self.atlas.len()
+    }
+
+    pub fn queue<'a>(
+        &mut self,
+        fonts: &'a Assets<Font>,
+        textures: &mut Assets<Image>,
+        texts: impl IntoIterator<Item = (&'a Text, Mat4)>,
+        batcher: &mut SpriteBatcher,
+    ) {
+        let prepared: Vec<PreparedText> = texts
+            .into_iter()
+            .filter(|(text, _)| text.visible && !text.content.is_empty())
+            .filter_map(|(text, transform)| {
+                let font = fonts.get(text.font)?;
+                Some(PreparedText {
+                    text,
+                    transform,
+                    font,
+                    layout: layout_text(font, &text.content, &text.style),
+                })
+            })
+            .collect();
+        if prepared.is_empty() {
+            return;
+        }
+        // A full atlas is rebuilt from just this frame's glyphs.
+        if !self.rasterize(&prepared) {
+            self.atlas.clear();
+            if !self.rasterize(&prepared) {
+                warn!("this frame's glyphs don't fit in the glyph atlas; some text is missing");
+            }
+        }
+        let texture = self.sync_texture(textures);
+        for text in &prepared {
+            self.emit(text, texture, batcher);
+        }
+    }
+
+    pub fn queue_scene(&mut self, scene: &mut Scene, batcher: &mut SpriteBatcher) {
+        let registry = &scene.registry;
+        let texts = registry
+            .iter::<Text>()
+            .map(|(entity, text)| (text, registry.world_matrix(entity)));
+        self.queue(&scene.fonts, &mut scene.textures, texts, batcher);
+    }
+
+    fn rasterize(&mut self, prepared: &[PreparedText]) -> bool {
+        for text in prepared {
+            for outline in passes(&text.text.style).map(|(outline, _)| outline) {
+                for glyph in &text.layout.glyphs {
+                    let key =
+                        GlyphKey::new(text.text.font, glyph.id, text.text.style.size, outline);
+                    if self.atlas.rasterize(key, text.font).is_err() {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn sync_texture(&mut self, textures: &mut Assets<Image>) -> TextureHandle {
+        match self.texture.filter(|&texture| textures.contains(texture)) {
+            Some(texture) => {
+                if self.atlas.take_dirty() {
+                    if let Some(image) = textures.get_mut(texture) {
+                        image.clone_from(self.atlas.image());
+                    }
+                }
+                texture
+            }
+            None => {
+                self.atlas.take_dirty();
+                let texture = textures.insert(self.atlas.image().clone());
+                self.texture = Some(texture);
+                texture
+            }
+        }
+    }
+
+    fn emit(&self, prepared: &PreparedText, texture: TextureHandle, batcher: &mut SpriteBatcher) {
+        let text = prepared.text;
+        let (scale, y_sign) = match text.space {
+            SpriteSpace::Screen => (1.0, 1.0),
+            SpriteSpace::World => (1.0 / text.pixels_per_unit, -1.0),
+        };
+        let origin = -text.pivot * prepared.layout.size;
+        // Outlines go first so every fill lands on top of its neighbours' strokes.
+        for (outline, color) in passes(&text.style) {
+            for glyph in &prepared.layout.glyphs {
+                let key = GlyphKey::new(text.font, glyph.id, text.style.size, outline);
+                let Some(entry) = self.atlas.get(&key) else {
+                    continue;
+                };
+                let top_left = (origin + glyph.position + entry.offset).round() * scale;
+                let sprite = Sprite {
+                    texture: Some(texture),
+                    uv: self.atlas.uv(&entry),
+                    size: Vec2::new(entry.width as f32, entry.height as f32) * scale,
+                    pivot: Vec2::ZERO,
+                    tint: color,
+                    z_order: text.z_order,
+                    space: text.space,
+                    sampler: Sampler::default(),
+                    visible: true,
+                };
+                let local = Mat4::from_translation(Vec3::new(top_left.x, top_left.y * y_sign, 0.0));
+                batcher.push(sprite, prepared.transform * local);
+            }
+        }
+    }
+}
+
+fn passes(style: &TextStyle) -> impl Iterator<Item = (f32, Vec4)> {
+    let outline = style
+        .outline
+        .filter(|outline| outline.width > 0.0)
+        .map(|outline| (outline.width, outline.color));
+    outline.into_iter().chain([(0.0, style.color)])
+}
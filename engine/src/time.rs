@@ -0,0 +1,40 @@
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step_seconds: f32,
+    max_substeps: u32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub fn new(update_hz: u32, max_substeps: u32) -> Self {
+        Self {
+            step_seconds: 1.0 / update_hz.max(1) as f32,
+            max_substeps: max_substeps.max(1),
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn step_seconds(&self) -> f32 {
+        self.step_seconds
+    }
+
+    pub fn advance(&mut self, delta_seconds: f32) -> u32 {
+        let max_backlog = self.step_seconds * self.max_substeps as f32;
+        self.accumulator += delta_seconds.clamp(0.0, max_backlog);
+        let mut steps = 0;
+        while self.accumulator >= self.step_seconds && steps < self.max_substeps {
+            self.accumulator -= self.step_seconds;
+            steps += 1;
+        }
+        // Anything still queued after hitting the substep cap is dropped so a slow frame
+        // cannot snowball into ever longer catch-up frames.
+        if self.accumulator >= self.step_seconds {
+            self.accumulator %= self.step_seconds;
+        }
+        steps
+    }
+
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step_seconds).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,196 @@
+use meme_engine::renderer::{
+    AnimationConfig, AnimationEncoder, AnimationFormat, BackendKind, Image, LoopMode,
+};
+use meme_engine::{Engine, EngineConfig, HeadlessConfig, RecordingConfig};
+use std::io::Cursor;
+
+const COLORS: [[u8; 4]; 3] = [[230, 40, 40, 255], [40, 200, 60, 255], [30, 60, 220, 255]];
+
+fn solid(color: [u8; 4]) -> Image {
+    Image::from_rgba(16, 8, color.repeat(16 * 8)).unwrap()
+}
+
+fn encode(format: AnimationFormat, loop_mode: LoopMode) -> Vec<u8> {
+    let mut encoder = AnimationEncoder::new(
+        Cursor::new(Vec::new()),
+        AnimationConfig {
+            loop_mode,
+            ..AnimationConfig::at_fps(format, 30)
+        },
+    );
+    for color in COLORS {
+        encoder.push_frame(solid(color)).unwrap();
+    }
+    encoder.finish().unwrap().into_inner()
+}
+
+#[test]
+fn gif_export_quantizes_frames_with_delays_and_loops() {
+    let bytes = encode(AnimationFormat::Gif, LoopMode::Forever);
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+    let mut delays = Vec::new();
+    let mut colors = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!((frame.width, frame.height), (16, 8));
+        delays.push(frame.delay);
+        colors.push([
+            frame.buffer[0],
+            frame.buffer[1],
+            frame.buffer[2],
+            frame.buffer[3],
+        ]);
+    }
+    assert_eq!(decoder.repeat(), gif::Repeat::Infinite);
+    // 1/30s is 3.33cs, so delays alternate to keep the total at 10cs.
+    assert_eq!(delays, [3, 4, 3]);
+    for (decoded, expected) in colors.iter().zip(COLORS) {
+        for channel in 0..3 {
+            assert!(
+                decoded[channel].abs_diff(expected[channel]) <= 8,
+                "{decoded:?}"
+            );
+        }
+    }
+
+    let once = encode(AnimationFormat::Gif, LoopMode::Times(1));
+    let decoder = gif::DecodeOptions::new()
+        .read_info(once.as_slice())
+        .unwrap();
+    assert_eq!(decoder.repeat(), gif::Repeat::Finite(0));
+}
+
+#[test]
+fn apng_export_keeps_exact_pixels() {
+    let bytes = encode(AnimationFormat::Apng, LoopMode::Times(3));
+    let mut reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+    let control = reader.info().animation_control.unwrap();
+    assert_eq!((control.num_frames, control.num_plays), (3, 3));
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    for (color, delay) in COLORS.into_iter().zip([33, 34, 33]) {
+        reader.next_frame(&mut buffer).unwrap();
+        let frame = reader.info().frame_control.unwrap();
+        assert_eq!((frame.delay_num, frame.delay_den), (delay, 1000));
+        assert_eq!(&buffer[..4], color);
+    }
+}
+
+#[test]
+fn frames_are_written_as_they_are_pushed() {
+    for format in [AnimationFormat::Gif, AnimationFormat::Apng] {
+        let output = std::env::temp_dir().join(format!(
+            "meme_stream_{}_{format:?}.anim",
+            std::process::id()
+        ));
+        let mut encoder =
+            AnimationEncoder::create(&output, AnimationConfig::at_fps(format, 30)).unwrap();
+        // Enough distinct frames to get past the GIF palette window and the
+        // file's write buffer.
+        let frames = 40;
+        for index in 0..frames {
+            let pixels = (0..64 * 64)
+                .flat_map(|pixel: u32| {
+                    let value = (pixel * 7 + index * 31) as u8;
+                    [value, value.wrapping_mul(3), 255 - value, 255]
+                })
+                .collect();
+            encoder
+                .push_frame(Image::from_rgba(64, 64, pixels).unwrap())
+                .unwrap();
+        }
+        assert!(std::fs::metadata(&output).unwrap().len() > 0, "{format:?}");
+        encoder.finish().unwrap();
+
+        let bytes = std::fs::read(&output).unwrap();
+        std::fs::remove_file(&output).ok();
+        let decoded = match format {
+            AnimationFormat::Gif => {
+                let mut decoder = gif::DecodeOptions::new()
+                    .read_info(bytes.as_slice())
+                    .unwrap();
+                let mut count = 0;
+                while decoder.read_next_frame().unwrap().is_some() {
+                    count += 1;
+                }
+                count
+            }
+            AnimationFormat::Apng => {
+                let mut reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+                let control = reader.info().animation_control.unwrap();
+                let mut buffer = vec![0; reader.output_buffer_size()];
+                for _ in 0..control.num_frames {
+                    reader.next_frame(&mut buffer).unwrap();
+                }
+                control.num_frames
+            }
+        };
+        assert_eq!(decoded, frames, "{format:?}");
+    }
+}
+
+#[test]
+fn animation_encoder_rejects_bad_input() {
+    assert_eq!(
+        AnimationFormat::from_path("out/meme.GIF"),
+        Some(AnimationFormat::Gif)
+    );
+    assert_eq!(
+        AnimationFormat::from_path("meme.apng"),
+        Some(AnimationFormat::Apng)
+    );
+    assert_eq!(AnimationFormat::from_path("meme.mp4"), None);
+
+    let empty = AnimationEncoder::new(Cursor::new(Vec::new()), AnimationConfig::default());
+    assert!(empty.finish().is_err());
+    let mut encoder = AnimationEncoder::new(Cursor::new(Vec::new()), AnimationConfig::default());
+    encoder.push_frame(solid(COLORS[0])).unwrap();
+    assert!(encoder.push_frame(Image::new(8, 8).unwrap()).is_err());
+    assert_eq!(encoder.len(), 1);
+
+    let config = EngineConfig {
+        recording: Some(RecordingConfig {
+            output: "recording.mp4".into(),
+            ..RecordingConfig::default()
+        }),
+        ..EngineConfig::default()
+    };
+    assert!(Engine::new(config).is_err());
+}
+
+#[test]
+fn headless_engine_records_every_nth_frame() {
+    let output = std::env::temp_dir().join(format!("meme_recording_{}.gif", std::process::id()));
+    let config = EngineConfig {
+        width: 64,
+        height: 48,
+        target_fps: 20,
+        headless: Some(HeadlessConfig {
+            max_frames: Some(6),
+            realtime: false,
+        }),
+        render_backends: vec![BackendKind::Software],
+        recording: Some(RecordingConfig {
+            output: output.clone(),
+            every_n_frames: 2,
+            loop_mode: LoopMode::Times(2),
+            ..RecordingConfig::default()
+        }),
+        ..EngineConfig::default()
+    };
+    Engine::new(config).unwrap().run().unwrap();
+
+    let bytes = std::fs::read(&output).unwrap();
+    std::fs::remove_file(&output).ok();
+    let mut decoder = gif::DecodeOptions::new()
+        .read_info(bytes.as_slice())
+        .unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (64, 48));
+    assert_eq!(decoder.repeat(), gif::Repeat::Finite(1));
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    // Frames 0, 2 and 4 at 20 fps are 100ms apart.
+    assert_eq!(delays, [10, 10, 10]);
+}
@@ -0,0 +1,138 @@
+use glam::{Quat, Vec3};
+use meme_engine::physics::{
+    BodyDesc, CharacterConfig, CharacterController, ColliderDesc, ColliderShape, PhysicsWorld, Pose,
+};
+
+const DT: f32 = 1.0 / 60.0;
+
+fn ground_world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    let ground = world.create_body(&BodyDesc::fixed(Vec3::new(0.0, -0.5, 0.0)));
+    world
+        .add_collider(ground, &ColliderDesc::cuboid(Vec3::new(20.0, 0.5, 20.0)))
+        .unwrap();
+    world
+}
+
+fn spawn_character(world: &mut PhysicsWorld, position: Vec3) -> CharacterController {
+    world
+        .create_character(
+            position,
+            ColliderShape::Capsule {
+                half_height: 0.5,
+                radius: 0.3,
+            },
+            CharacterConfig::default(),
+        )
+        .unwrap()
+}
+
+fn simulate(
+    world: &mut PhysicsWorld,
+    character: &mut CharacterController,
+    velocity: Vec3,
+    steps: usize,
+) {
+    for _ in 0..steps {
+        world.move_character(character, velocity, DT);
+        world.step(DT);
+    }
+}
+
+#[test]
+fn falls_to_the_ground_and_walks() {
+    let mut world = ground_world();
+    let mut character = spawn_character(&mut world, Vec3::new(0.0, 3.0, 0.0));
+    assert!(!character.is_grounded());
+
+    simulate(&mut world, &mut character, Vec3::ZERO, 120);
+    assert!(character.is_grounded());
+    assert!((character.position().y - 0.8).abs() < 0.05);
+    assert!(character.state().ground.is_some());
+    assert!((character.state().ground_normal.unwrap() - Vec3::Y).length() < 1e-3);
+
+    simulate(&mut world, &mut character, Vec3::new(2.0, 0.0, 0.0), 60);
+    assert!((character.position().x - 2.0).abs() < 0.05);
+    assert!(character.is_grounded());
+    let body = world.body_pose(character.body()).unwrap();
+    assert!((body.position - character.position()).length() < 1e-4);
+}
+
+#[test]
+fn steps_up_ledges_but_not_walls() {
+    let mut world = ground_world();
+    let step = world.create_body(&BodyDesc::fixed(Vec3::new(2.0, 0.1, 0.0)));
+    world
+        .add_collider(step, &ColliderDesc::cuboid(Vec3::new(0.5, 0.1, 2.0)))
+        .unwrap();
+    let wall = world.create_body(&BodyDesc::fixed(Vec3::new(2.0, 1.0, 5.0)));
+    world
+        .add_collider(wall, &ColliderDesc::cuboid(Vec3::new(0.5, 1.0, 2.0)))
+        .unwrap();
+    world.update_queries();
+
+    let mut climber = spawn_character(&mut world, Vec3::new(0.0, 0.8, 0.0));
+    let mut blocked = spawn_character(&mut world, Vec3::new(0.0, 0.8, 5.0));
+    for _ in 0..90 {
+        world.move_character(&mut climber, Vec3::new(1.5, 0.0, 0.0), DT);
+        world.move_character(&mut blocked, Vec3::new(1.5, 0.0, 0.0), DT);
+        world.step(DT);
+    }
+
+    assert!(climber.position().x > 1.8, "{}", climber.position());
+    assert!((climber.position().y - 1.0).abs() < 0.05);
+    assert!(blocked.position().x < 1.3, "{}", blocked.position());
+}
+
+#[test]
+fn slope_limit_stops_climbing_steep_ramps() {
+    let mut world = ground_world();
+    let ramp = world.create_body(&BodyDesc {
+        pose: Pose {
+            position: Vec3::new(4.0, 0.0, 0.0),
+            rotation: Quat::from_rotation_z(60f32.to_radians()),
+        },
+        ..BodyDesc::fixed(Vec3::ZERO)
+    });
+    world
+        .add_collider(ramp, &ColliderDesc::cuboid(Vec3::new(3.0, 0.1, 2.0)))
+        .unwrap();
+    world.update_queries();
+
+    let mut character = spawn_character(&mut world, Vec3::new(0.0, 0.8, 0.0));
+    simulate(&mut world, &mut character, Vec3::new(2.0, 0.0, 0.0), 180);
+    assert!(character.position().y < 1.5, "{}", character.position());
+}
+
+#[test]
+fn rides_moving_platforms() {
+    let mut world = PhysicsWorld::new();
+    let platform = world.create_body(&BodyDesc::kinematic(Vec3::new(0.0, -0.5, 0.0)));
+    world
+        .add_collider(platform, &ColliderDesc::cuboid(Vec3::new(2.0, 0.5, 2.0)))
+        .unwrap();
+    world.update_queries();
+    let mut character = spawn_character(&mut world, Vec3::new(0.0, 0.85, 0.0));
+
+    simulate(&mut world, &mut character, Vec3::ZERO, 30);
+    assert_eq!(
+        world.collider_body(character.state().ground.unwrap()),
+        Some(platform)
+    );
+
+    for frame in 1..=60 {
+        let target = Pose {
+            position: Vec3::new(frame as f32 * DT, -0.5, 0.0),
+            ..Pose::default()
+        };
+        world.set_kinematic_target(platform, target);
+        world.move_character(&mut character, Vec3::ZERO, DT);
+        world.step(DT);
+    }
+    assert!(
+        (character.position().x - 1.0).abs() < 0.1,
+        "{}",
+        character.position()
+    );
+    assert!(character.is_grounded());
+}
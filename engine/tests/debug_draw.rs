@@ -0,0 +1,92 @@
+use glam::{Mat4, Vec3, Vec4};
+use meme_engine::debug_draw::DebugDraw;
+use meme_engine::physics::{BodyDesc, ColliderDesc, PhysicsDebugOptions, PhysicsWorld};
+use meme_engine::renderer::{BackendKind, RenderFrame, Renderer};
+
+#[test]
+fn aabb_emits_twelve_edges() {
+    let mut draw = DebugDraw::new(true);
+    draw.aabb(Vec3::splat(-1.0), Vec3::splat(1.0), Vec4::ONE);
+    assert_eq!(draw.len(), 12);
+    for line in draw.lines() {
+        assert!(((line.end - line.start).length() - 2.0).abs() < 1.0e-6);
+    }
+}
+
+#[test]
+fn physics_emits_lines_for_enabled_categories() {
+    let mut world = PhysicsWorld::new();
+    let body = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    world
+        .add_collider(body, &ColliderDesc::cuboid(Vec3::splat(0.5)))
+        .unwrap();
+    world.step(1.0 / 60.0);
+
+    let mut shapes = DebugDraw::default();
+    let options = PhysicsDebugOptions {
+        colliders: true,
+        contacts: false,
+        joints: false,
+        aabbs: false,
+        body_axes: false,
+    };
+    world.debug_draw(&options, &mut shapes);
+    assert!(!shapes.is_empty());
+    for line in shapes.lines() {
+        assert!(line.start.abs().max_element() <= 0.5 + 1.0e-4);
+    }
+
+    let mut with_aabbs = DebugDraw::default();
+    world.debug_draw(
+        &PhysicsDebugOptions {
+            aabbs: true,
+            ..options
+        },
+        &mut with_aabbs,
+    );
+    assert_eq!(with_aabbs.len(), shapes.len() + 12);
+
+    let mut nothing = DebugDraw::default();
+    world.debug_draw(
+        &PhysicsDebugOptions {
+            colliders: false,
+            ..options
+        },
+        &mut nothing,
+    );
+    assert!(nothing.is_empty());
+}
+
+#[test]
+fn software_renderer_draws_lines_over_the_scene() {
+    let clear_color = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let mut draw = DebugDraw::default();
+    draw.line(
+        Vec3::new(-1.5, 0.9, -0.5),
+        Vec3::new(1.5, 0.9, -0.5),
+        Vec4::new(1.0, 0.0, 0.0, 1.0),
+    );
+    draw.line(
+        Vec3::new(-0.5, -0.9, 0.5),
+        Vec3::new(-0.5, 0.9, 3.0),
+        Vec4::new(0.0, 1.0, 0.0, 1.0),
+    );
+    let frame = RenderFrame {
+        clear_color,
+        view_projection: Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 1.0),
+        debug_lines: draw.lines().to_vec(),
+        ..RenderFrame::default()
+    };
+    let mut renderer = Renderer::offscreen(64, 64, &[BackendKind::Software]).unwrap();
+    renderer.render(&frame).unwrap();
+    let image = renderer.capture_frame().unwrap();
+
+    let red = (0..64)
+        .filter(|&x| image.pixel(x, 3) == [255, 0, 0, 255])
+        .count();
+    assert_eq!(red, 64);
+    let green = (0..64)
+        .filter(|&y| image.pixel(16, y) == [0, 255, 0, 255])
+        .count();
+    assert_eq!(green, 0, "lines behind the camera must be clipped");
+}
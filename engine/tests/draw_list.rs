@@ -0,0 +1,95 @@
+use glam::{Mat4, Quat, Vec3, Vec4};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{
+    collect_draw_items, BackendKind, DrawItem, Material, Mesh, MeshRenderer, RenderFrame, Renderer,
+};
+use meme_engine::scene::{Camera, Scene, Transform};
+
+#[test]
+fn draw_items_follow_hierarchy_and_materials() {
+    let mut scene = Scene::default();
+    let cube = scene.meshes.insert(Mesh::cuboid(Vec3::splat(0.5)));
+    let red = scene
+        .materials
+        .insert(Material::color(Vec4::new(1.0, 0.0, 0.0, 1.0)));
+
+    let parent = scene.registry.spawn((
+        Transform::from_position(Vec3::new(2.0, 0.0, 0.0)),
+        MeshRenderer::new(cube),
+    ));
+    let child = scene.registry.spawn((
+        Transform::from_position(Vec3::new(0.0, 1.0, 0.0)),
+        MeshRenderer::new(cube).with_material(red),
+    ));
+    scene.registry.set_parent(child, Some(parent)).unwrap();
+    scene.registry.spawn((
+        Transform::default(),
+        MeshRenderer {
+            visible: false,
+            ..MeshRenderer::new(cube)
+        },
+    ));
+
+    let mut items = collect_draw_items(&scene);
+    items.sort_by(|a, b| a.world.w_axis.y.total_cmp(&b.world.w_axis.y));
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].material, Material::default());
+    assert_eq!(items[1].material.base_color, Vec4::new(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(items[1].world.w_axis.truncate(), Vec3::new(2.0, 1.0, 0.0));
+}
+
+#[test]
+fn backend_renders_the_draw_list_with_the_camera() {
+    let mut meshes = Assets::new();
+    let cube = meshes.insert(Mesh::cuboid(Vec3::splat(0.5)));
+    let removed = meshes.insert(Mesh::cuboid(Vec3::splat(0.5)));
+    meshes.remove(removed);
+
+    let camera = Camera {
+        position: Vec3::new(0.0, 0.0, -4.0),
+        aspect_ratio: 1.0,
+        ..Camera::default()
+    };
+    let clear_color = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let frame = RenderFrame {
+        clear_color,
+        view_projection: camera.view_projection(),
+        draw_items: vec![
+            DrawItem {
+                material: Material::color(Vec4::new(0.0, 1.0, 0.0, 1.0)),
+                ..DrawItem::new(
+                    cube,
+                    Mat4::from_rotation_translation(Quat::IDENTITY, Vec3::new(1.0, 0.0, 0.0)),
+                )
+            },
+            DrawItem::new(removed, Mat4::IDENTITY),
+        ],
+        ..RenderFrame::default()
+    };
+    let mut renderer = Renderer::offscreen(64, 64, &[BackendKind::Software]).unwrap();
+    renderer.sync_meshes(&meshes).unwrap();
+    renderer.render(&frame).unwrap();
+    let image = renderer.capture_frame().unwrap();
+
+    // +X appears on the left when looking down +Z with a right-handed camera.
+    assert_eq!(image.pixel(20, 32), [0, 255, 0, 255]);
+    assert_eq!(image.pixel(44, 32), [0, 0, 0, 255]);
+}
+
+#[test]
+fn backend_capabilities_match_capture_support() {
+    for kind in [BackendKind::Software, BackendKind::Null] {
+        let mut renderer = Renderer::offscreen(8, 8, &[kind]).unwrap();
+        renderer.render(&RenderFrame::default()).unwrap();
+        assert_eq!(
+            renderer.capabilities().offscreen,
+            renderer.capture_frame().is_ok(),
+            "{kind}"
+        );
+    }
+}
+
+#[test]
+fn software_framebuffers_that_overflow_are_errors() {
+    assert!(Renderer::offscreen(u32::MAX, u32::MAX, &[BackendKind::Software]).is_err());
+}
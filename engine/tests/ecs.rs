@@ -0,0 +1,123 @@
+use meme_engine::scene::ecs::{Commands, Registry, With, Without};
+use meme_engine::scene::Transform;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Velocity(f32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Frozen;
+
+#[test]
+fn despawned_entities_are_recycled_with_new_generation() {
+    let mut registry = Registry::new();
+    let first = registry.spawn((Velocity(1.0),));
+    assert!(registry.despawn(first));
+    assert!(!registry.is_alive(first));
+    assert!(!registry.despawn(first));
+
+    let second = registry.spawn(());
+    assert_eq!(second.index(), first.index());
+    assert_ne!(second.generation(), first.generation());
+    assert!(registry.get::<Velocity>(second).is_none());
+    assert!(registry.get::<Velocity>(first).is_none());
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn tuple_queries_visit_matching_entities() {
+    let mut registry = Registry::new();
+    let moving = registry.spawn((Transform::default(), Velocity(2.0)));
+    let frozen = registry.spawn((Transform::default(), Velocity(5.0), Frozen));
+    let still = registry.spawn((Transform::default(),));
+
+    for (_, (transform, velocity, _)) in
+        registry.query::<(&mut Transform, &Velocity, Without<Frozen>)>()
+    {
+        transform.position.x += velocity.0;
+    }
+
+    assert_eq!(registry.get::<Transform>(moving).unwrap().position.x, 2.0);
+    assert_eq!(registry.get::<Transform>(frozen).unwrap().position.x, 0.0);
+    assert_eq!(registry.get::<Transform>(still).unwrap().position.x, 0.0);
+
+    let frozen_entities: Vec<_> = registry
+        .query::<(&Velocity, With<Frozen>)>()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(frozen_entities, vec![frozen]);
+
+    let with_optional: Vec<_> = registry
+        .query::<(&Transform, Option<&Velocity>)>()
+        .map(|(entity, (_, velocity))| (entity, velocity.copied()))
+        .collect();
+    assert_eq!(with_optional.len(), 3);
+    assert!(with_optional.contains(&(still, None)));
+}
+
+#[test]
+fn query_one_reads_single_entity() {
+    let mut registry = Registry::new();
+    let entity = registry.spawn((Velocity(3.0), Transform::default()));
+    let (velocity, transform) = registry
+        .query_one::<(&mut Velocity, &Transform)>(entity)
+        .unwrap();
+    velocity.0 += transform.scale.x;
+    assert_eq!(registry.get::<Velocity>(entity), Some(&Velocity(4.0)));
+}
+
+#[test]
+fn mutable_query_items_can_be_held_together() {
+    let mut registry = Registry::new();
+    let entities: Vec<_> = (0..4)
+        .map(|index| registry.spawn((Velocity(index as f32),)))
+        .collect();
+    registry.insert(entities[3], Frozen);
+
+    // Every item stays alive while later ones are fetched, including filter
+    // lookups into the storage the items point into.
+    let items: Vec<_> = registry
+        .query::<(&mut Velocity, With<Velocity>, Without<Frozen>)>()
+        .map(|(_, (velocity, _, _))| velocity)
+        .collect();
+    assert_eq!(items.len(), 3);
+    for velocity in items {
+        velocity.0 += 10.0;
+    }
+    let velocities: Vec<_> = entities
+        .iter()
+        .map(|&entity| registry.get::<Velocity>(entity).unwrap().0)
+        .collect();
+    assert_eq!(velocities, [10.0, 11.0, 12.0, 3.0]);
+}
+
+#[test]
+#[should_panic(expected = "mutably more than once")]
+fn aliasing_queries_are_rejected() {
+    let mut registry = Registry::new();
+    registry.spawn((Velocity(1.0),));
+    let _ = registry.query::<(&mut Velocity, &Velocity)>().count();
+}
+
+#[test]
+fn commands_apply_deferred_changes() {
+    let mut registry = Registry::new();
+    let a = registry.spawn((Velocity(1.0),));
+    let b = registry.spawn((Velocity(-1.0),));
+
+    let mut commands = Commands::new();
+    for (entity, velocity) in registry.query::<&Velocity>() {
+        if velocity.0 < 0.0 {
+            commands.despawn(entity);
+        } else {
+            commands.insert(entity, Frozen);
+        }
+    }
+    commands.spawn((Velocity(9.0),));
+    assert_eq!(commands.len(), 3);
+    commands.apply(&mut registry);
+
+    assert!(commands.is_empty());
+    assert!(registry.has::<Frozen>(a));
+    assert!(!registry.is_alive(b));
+    assert_eq!(registry.iter::<Velocity>().count(), 2);
+}
@@ -0,0 +1,58 @@
+use meme_engine::{Engine, EngineConfig, EngineContext, EngineEvent, HeadlessConfig};
+use std::sync::{Arc, Mutex};
+
+fn headless_config(max_frames: Option<u64>) -> EngineConfig {
+    EngineConfig {
+        target_fps: 30,
+        fixed_update_hz: 60,
+        headless: Some(HeadlessConfig {
+            max_frames,
+            realtime: false,
+        }),
+        ..EngineConfig::default()
+    }
+}
+
+#[test]
+fn headless_run_emits_lifecycle_events_in_order() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let engine = Engine::new(headless_config(Some(3))).unwrap();
+    engine
+        .run_with(move |event: &EngineEvent, _ctx: &mut EngineContext<'_>| {
+            let name = match event {
+                EngineEvent::Startup => "startup",
+                EngineEvent::FixedUpdate { .. } => "fixed",
+                EngineEvent::Frame { .. } => "frame",
+                EngineEvent::Shutdown => "shutdown",
+            };
+            recorded.lock().unwrap().push(name);
+        })
+        .unwrap();
+
+    let events = events.lock().unwrap();
+    let mut expected = vec!["startup"];
+    for _ in 0..3 {
+        expected.extend(["fixed", "fixed", "frame"]);
+    }
+    expected.push("shutdown");
+    assert_eq!(*events, expected);
+}
+
+#[test]
+fn game_can_request_stop() {
+    let frames = Arc::new(Mutex::new(0));
+    let counted = frames.clone();
+    let engine = Engine::new(headless_config(None)).unwrap();
+    engine
+        .run_with(move |event: &EngineEvent, ctx: &mut EngineContext<'_>| {
+            if let EngineEvent::Frame { .. } = event {
+                *counted.lock().unwrap() += 1;
+                if ctx.frame_index == 9 {
+                    ctx.request_stop();
+                }
+            }
+        })
+        .unwrap();
+    assert_eq!(*frames.lock().unwrap(), 10);
+}
@@ -0,0 +1,74 @@
+mod support;
+
+use glam::Vec4;
+use meme_engine::assets::Assets;
+use meme_engine::renderer::RenderFrame;
+use support::{
+    assert_golden, assert_images_match, render_offscreen, render_scene, spinning_cube_frame,
+    Tolerance,
+};
+
+#[test]
+fn clear_color_only() {
+    let frame = RenderFrame {
+        clear_color: Vec4::new(0.25, 0.5, 0.75, 1.0),
+        time_seconds: 0.0,
+        ..RenderFrame::default()
+    };
+    let image = render_offscreen(64, 64, &frame);
+    // Nothing but the clear color, so the reference can't quietly pick up geometry.
+    for y in 0..64 {
+        for x in 0..64 {
+            assert_eq!(image.pixel(x, y), [64, 128, 191, 255], "pixel {x},{y}");
+        }
+    }
+    assert_golden("clear_color_only", &image, Tolerance::default());
+}
+
+#[test]
+fn cube_at_rest() {
+    let mut meshes = Assets::new();
+    let frame = spinning_cube_frame(&mut meshes, Vec4::new(0.08, 0.09, 0.14, 1.0), 0.0, 160, 90);
+    let image = render_scene(160, 90, &meshes, &frame);
+    assert_golden("cube_at_rest", &image, Tolerance::default());
+}
+
+#[test]
+fn cube_rotated() {
+    let mut meshes = Assets::new();
+    let frame = spinning_cube_frame(&mut meshes, Vec4::new(0.08, 0.09, 0.14, 1.0), 1.3, 160, 90);
+    let image = render_scene(160, 90, &meshes, &frame);
+    assert_golden("cube_rotated", &image, Tolerance::default());
+}
+
+#[test]
+fn cube_square_viewport() {
+    let mut meshes = Assets::new();
+    let frame = spinning_cube_frame(&mut meshes, Vec4::new(1.0, 1.0, 1.0, 1.0), 2.7, 96, 96);
+    let image = render_scene(96, 96, &meshes, &frame);
+    assert_golden("cube_square_viewport", &image, Tolerance::default());
+}
+
+#[test]
+#[should_panic(expected = "pixels differ")]
+fn mismatch_is_reported() {
+    let expected = render_offscreen(
+        64,
+        64,
+        &RenderFrame {
+            clear_color: Vec4::new(0.25, 0.5, 0.75, 1.0),
+            time_seconds: 0.0,
+            ..RenderFrame::default()
+        },
+    );
+    let actual = render_offscreen(
+        64,
+        64,
+        &RenderFrame {
+            clear_color: Vec4::new(0.25, 0.5, 0.8, 1.0),
+            time_seconds: 0.0,
+            ..RenderFrame::default()
+        },
+    );
+    assert_images_match("mismatch_is_reported", &expected, &actual, Tolerance::default());
+}
@@ -0,0 +1,135 @@
+use glam::{Quat, Vec3};
+use meme_engine::scene::{propagate_transforms, Children, GlobalTransform, Registry, Transform};
+
+fn world_position(registry: &Registry, entity: meme_engine::scene::Entity) -> Vec3 {
+    registry
+        .get::<GlobalTransform>(entity)
+        .expect("global transform")
+        .to_transform()
+        .position
+}
+
+#[test]
+fn children_follow_their_parent() {
+    let mut registry = Registry::new();
+    let character = registry.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+    let hat = registry.spawn((Transform::from_position(Vec3::new(0.0, 2.0, 0.0)),));
+    registry.set_parent(hat, Some(character)).unwrap();
+
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, hat).abs_diff_eq(Vec3::new(10.0, 2.0, 0.0), 1e-5));
+
+    registry.get_mut::<Transform>(character).unwrap().position.x = -4.0;
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, hat).abs_diff_eq(Vec3::new(-4.0, 2.0, 0.0), 1e-5));
+}
+
+#[test]
+fn rotation_and_scale_propagate_through_levels() {
+    let mut registry = Registry::new();
+    let root = registry.spawn((Transform {
+        rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        scale: Vec3::splat(2.0),
+        ..Transform::default()
+    },));
+    let middle = registry.spawn((Transform::from_position(Vec3::X),));
+    let leaf = registry.spawn((Transform::from_position(Vec3::X),));
+    registry.set_parent(middle, Some(root)).unwrap();
+    registry.set_parent(leaf, Some(middle)).unwrap();
+
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, leaf).abs_diff_eq(Vec3::new(0.0, 0.0, -4.0), 1e-5));
+    assert!(registry
+        .world_matrix(leaf)
+        .abs_diff_eq(registry.get::<GlobalTransform>(leaf).unwrap().matrix(), 1e-5));
+}
+
+#[test]
+fn reparenting_can_preserve_world_position() {
+    let mut registry = Registry::new();
+    let left = registry.spawn((Transform::from_position(Vec3::new(-5.0, 0.0, 0.0)),));
+    let right = registry.spawn((Transform {
+        position: Vec3::new(5.0, 1.0, 0.0),
+        rotation: Quat::from_rotation_z(0.7),
+        ..Transform::default()
+    },));
+    let prop = registry.spawn((Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),));
+    registry.set_parent(prop, Some(left)).unwrap();
+    propagate_transforms(&mut registry);
+    let before = world_position(&registry, prop);
+
+    registry.set_parent_keep_world(prop, Some(right)).unwrap();
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, prop).abs_diff_eq(before, 1e-4));
+    assert!(registry.get::<Children>(left).unwrap().is_empty());
+    assert_eq!(registry.parent(prop), Some(right));
+
+    registry.set_parent(prop, None).unwrap();
+    propagate_transforms(&mut registry);
+    let local = registry.get::<Transform>(prop).unwrap().position;
+    assert!(world_position(&registry, prop).abs_diff_eq(local, 1e-5));
+}
+
+#[test]
+fn cycles_are_rejected_and_recursive_despawn_cleans_up() {
+    let mut registry = Registry::new();
+    let a = registry.spawn((Transform::default(),));
+    let b = registry.spawn((Transform::default(),));
+    let c = registry.spawn((Transform::default(),));
+    registry.set_parent(b, Some(a)).unwrap();
+    registry.set_parent(c, Some(b)).unwrap();
+    assert!(registry.set_parent(a, Some(c)).is_err());
+    assert!(registry.set_parent(a, Some(a)).is_err());
+
+    assert!(registry.despawn_recursive(b));
+    assert!(registry.is_alive(a));
+    assert!(!registry.is_alive(b));
+    assert!(!registry.is_alive(c));
+    assert!(registry.get::<Children>(a).unwrap().is_empty());
+}
+
+#[test]
+fn despawn_unlinks_parents_and_children() {
+    let mut registry = Registry::new();
+    let parent = registry.spawn((Transform::from_position(Vec3::new(3.0, 0.0, 0.0)),));
+    let left = registry.spawn((Transform::from_position(Vec3::Y),));
+    let right = registry.spawn((Transform::from_position(Vec3::Z),));
+    registry.set_parent(left, Some(parent)).unwrap();
+    registry.set_parent(right, Some(parent)).unwrap();
+    propagate_transforms(&mut registry);
+
+    assert!(registry.despawn(left));
+    let children: Vec<_> = registry.get::<Children>(parent).unwrap().iter().collect();
+    assert_eq!(children, [right]);
+
+    // The orphan becomes a root and its global transform drops the parent's offset.
+    assert!(registry.despawn(parent));
+    assert_eq!(registry.parent(right), None);
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, right).abs_diff_eq(Vec3::Z, 1e-5));
+}
+
+#[test]
+fn parents_without_transforms_start_new_roots() {
+    let mut registry = Registry::new();
+    let grandparent = registry.spawn((Transform::from_position(Vec3::new(5.0, 0.0, 0.0)),));
+    let group = registry.spawn(());
+    let child = registry.spawn((Transform::from_position(Vec3::Y),));
+    registry.set_parent(group, Some(grandparent)).unwrap();
+    registry.set_parent(child, Some(group)).unwrap();
+
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, child).abs_diff_eq(Vec3::Y, 1e-5));
+    assert!(registry.world_matrix(child).abs_diff_eq(
+        registry.get::<GlobalTransform>(child).unwrap().matrix(),
+        1e-5
+    ));
+
+    registry.insert(group, Transform::from_position(Vec3::Z));
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, child).abs_diff_eq(Vec3::new(5.0, 1.0, 1.0), 1e-5));
+
+    registry.remove::<Transform>(group);
+    propagate_transforms(&mut registry);
+    assert!(world_position(&registry, child).abs_diff_eq(Vec3::Y, 1e-5));
+}
@@ -0,0 +1,189 @@
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{
+    AlphaMode, BackendKind, DrawItem, FilterMode, Image, Material, Mesh, RenderFrame, Renderer,
+    Sampler, WrapMode,
+};
+
+fn quadrant_texture() -> Image {
+    let mut image = Image::new(2, 2).unwrap();
+    image.set_pixel(0, 0, [255, 0, 0, 255]);
+    image.set_pixel(1, 0, [0, 255, 0, 255]);
+    image.set_pixel(0, 1, [0, 0, 255, 255]);
+    image.set_pixel(1, 1, [255, 255, 255, 255]);
+    image
+}
+
+fn render_quads(textures: &Assets<Image>, items: Vec<(Material, Mat4)>) -> Image {
+    render_mesh(Mesh::quad(Vec2::splat(2.0)), textures, items)
+}
+
+fn render_mesh(mesh: Mesh, textures: &Assets<Image>, items: Vec<(Material, Mat4)>) -> Image {
+    let mut meshes = Assets::new();
+    let quad = meshes.insert(mesh);
+    let frame = RenderFrame {
+        clear_color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        view_projection: Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, -1.0, 1.0),
+        draw_items: items
+            .into_iter()
+            .map(|(material, world)| DrawItem {
+                material,
+                ..DrawItem::new(quad, world)
+            })
+            .collect(),
+        ..RenderFrame::default()
+    };
+    let mut renderer = Renderer::offscreen(64, 64, &[BackendKind::Software]).unwrap();
+    renderer.sync_meshes(&meshes).unwrap();
+    renderer.sync_textures(textures).unwrap();
+    renderer.render(&frame).unwrap();
+    renderer.capture_frame().unwrap()
+}
+
+#[test]
+fn images_decode_from_png_and_jpeg() {
+    let png = quadrant_texture().encode_png().unwrap();
+    assert_eq!(Image::decode(&png).unwrap(), quadrant_texture());
+
+    let mut jpeg = Vec::new();
+    let rgb: Vec<u8> = [200u8, 40, 40].repeat(16 * 16);
+    jpeg_encoder::Encoder::new(&mut jpeg, 100)
+        .encode(&rgb, 16, 16, jpeg_encoder::ColorType::Rgb)
+        .unwrap();
+    let decoded = Image::decode(&jpeg).unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (16, 16));
+    let [r, g, b, a] = decoded.pixel(8, 8);
+    assert!(r.abs_diff(200) <= 3 && g.abs_diff(40) <= 3 && b.abs_diff(40) <= 3);
+    assert_eq!(a, 255);
+
+    assert!(Image::decode(b"GIF89a").is_err());
+}
+
+#[test]
+fn image_sizes_that_overflow_are_errors() {
+    // Header sizes whose byte count overflows are errors, not panics.
+    assert!(Image::new(u32::MAX, u32::MAX).is_err());
+    assert!(Image::from_rgba(u32::MAX, u32::MAX, Vec::new()).is_err());
+    assert!(Image::from_rgba(65536, 65536, Vec::new()).is_err());
+}
+
+#[test]
+fn textured_quad_uses_the_material_sampler() {
+    let mut textures = Assets::new();
+    let texture = textures.insert(quadrant_texture());
+    let nearest = Material {
+        sampler: Sampler::new(FilterMode::Nearest, WrapMode::Clamp),
+        ..Material::textured(texture)
+    };
+
+    let image = render_quads(&textures, vec![(nearest, Mat4::IDENTITY)]);
+    assert_eq!(image.pixel(16, 16), [255, 0, 0, 255]);
+    assert_eq!(image.pixel(48, 16), [0, 255, 0, 255]);
+    assert_eq!(image.pixel(16, 48), [0, 0, 255, 255]);
+    assert_eq!(image.pixel(48, 48), [255, 255, 255, 255]);
+
+    let tinted = Material {
+        base_color: Vec4::new(0.5, 1.0, 1.0, 1.0),
+        ..Material::textured(texture)
+    };
+    let image = render_quads(&textures, vec![(tinted, Mat4::IDENTITY)]);
+    let [r, g, b, _] = image.pixel(32, 16);
+    assert!((50..80).contains(&r) && (100..160).contains(&g) && b < 10);
+
+    let mut tiled = Mesh::quad(Vec2::splat(2.0));
+    tiled.uvs.iter_mut().for_each(|uv| *uv *= 2.0);
+    let sampled = |wrap| {
+        let material = Material {
+            sampler: Sampler::new(FilterMode::Nearest, wrap),
+            ..Material::textured(texture)
+        };
+        render_mesh(tiled.clone(), &textures, vec![(material, Mat4::IDENTITY)]).pixel(40, 8)
+    };
+    assert_eq!(sampled(WrapMode::Clamp), [0, 255, 0, 255]);
+    assert_eq!(sampled(WrapMode::Repeat), [255, 0, 0, 255]);
+    assert_eq!(sampled(WrapMode::Mirror), [0, 255, 0, 255]);
+}
+
+#[test]
+fn alpha_modes_and_two_sided_materials() {
+    let mut textures = Assets::new();
+    let mut cutout = Image::new(2, 1).unwrap();
+    cutout.set_pixel(0, 0, [255, 255, 255, 0]);
+    cutout.set_pixel(1, 0, [255, 255, 255, 255]);
+    let cutout = textures.insert(cutout);
+
+    let masked = Material {
+        sampler: Sampler::new(FilterMode::Nearest, WrapMode::Clamp),
+        alpha_mode: AlphaMode::Mask { cutoff: 0.5 },
+        ..Material::textured(cutout)
+    };
+    let image = render_quads(&textures, vec![(masked, Mat4::IDENTITY)]);
+    assert_eq!(image.pixel(16, 32), [0, 0, 0, 255]);
+    assert_eq!(image.pixel(48, 32), [255, 255, 255, 255]);
+
+    // The blended quad sits behind the opaque one in the list but in front in
+    // depth, so it has to be drawn last to mix with it.
+    let blended = Material {
+        base_color: Vec4::new(1.0, 0.0, 0.0, 0.5),
+        alpha_mode: AlphaMode::Blend,
+        ..Material::default()
+    };
+    let blue = Material::color(Vec4::new(0.0, 0.0, 1.0, 1.0));
+    let image = render_quads(
+        &textures,
+        vec![
+            (blended, Mat4::from_translation(Vec3::new(0.0, 0.0, 0.5))),
+            (blue, Mat4::IDENTITY),
+        ],
+    );
+    assert_eq!(image.pixel(32, 32), [128, 0, 128, 255]);
+
+    let flipped = Mat4::from_quat(Quat::from_rotation_y(std::f32::consts::PI));
+    let image = render_quads(&textures, vec![(Material::default(), flipped)]);
+    assert_eq!(image.pixel(32, 32), [0, 0, 0, 255]);
+    let two_sided = Material {
+        two_sided: true,
+        ..Material::default()
+    };
+    let image = render_quads(&textures, vec![(two_sided, flipped)]);
+    assert_eq!(image.pixel(32, 32), [255, 255, 255, 255]);
+}
+
+#[test]
+fn blended_quads_shade_shared_edges_once() {
+    // The quad's diagonal runs through pixel centers, so without a fill rule
+    // both triangles would blend onto it.
+    let half_white = Material {
+        base_color: Vec4::new(1.0, 1.0, 1.0, 0.5),
+        alpha_mode: AlphaMode::Blend,
+        ..Material::default()
+    };
+    let image = render_quads(&Assets::new(), vec![(half_white, Mat4::IDENTITY)]);
+    for y in 0..64 {
+        for x in 0..64 {
+            assert_eq!(image.pixel(x, y), [128, 128, 128, 255], "pixel {x},{y}");
+        }
+    }
+}
+
+#[test]
+fn renderer_tracks_texture_uploads() {
+    let mut renderer = Renderer::offscreen(16, 16, &[BackendKind::Software]).unwrap();
+    let mut textures = Assets::new();
+    let first = textures.insert(quadrant_texture());
+    let second = textures.insert(Image::new(4, 4).unwrap());
+
+    assert_eq!(renderer.sync_textures(&textures).unwrap(), 2);
+    assert_eq!(renderer.sync_textures(&textures).unwrap(), 0);
+    textures.get_mut(first).unwrap().set_pixel(0, 0, [0; 4]);
+    assert_eq!(renderer.sync_textures(&textures).unwrap(), 1);
+
+    textures.remove(second);
+    renderer.sync_textures(&textures).unwrap();
+    assert!(!renderer.is_texture_uploaded(second));
+
+    let empty = textures.insert(Image::new(0, 0).unwrap());
+    assert!(renderer.sync_textures(&textures).is_err());
+    assert!(!renderer.is_texture_uploaded(empty));
+    assert!(renderer.is_texture_uploaded(first));
+}
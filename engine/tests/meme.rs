@@ -0,0 +1,88 @@
+use glam::Vec2;
+use meme_engine::meme::{Caption, CaptionPosition, MemeCompositor, MemeConfig};
+use meme_engine::renderer::{BackendKind, Image};
+use meme_engine::text::Font;
+
+const FONT_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../assets/fonts/DejaVuSans-Bold.ttf"
+);
+
+fn compositor() -> MemeCompositor {
+    MemeCompositor::new(
+        Font::load(FONT_PATH).unwrap(),
+        MemeConfig {
+            render_backends: vec![BackendKind::Software],
+            ..MemeConfig::default()
+        },
+    )
+}
+
+fn gradient(width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height).unwrap();
+    for y in 0..height {
+        for x in 0..width {
+            image.set_pixel(
+                x,
+                y,
+                [(x * 255 / width) as u8, (y * 255 / height) as u8, 96, 255],
+            );
+        }
+    }
+    image
+}
+
+fn rows_with(image: &Image, rows: std::ops::Range<u32>, test: impl Fn([u8; 4]) -> bool) -> bool {
+    rows.into_iter()
+        .any(|y| (0..image.width()).any(|x| test(image.pixel(x, y))))
+}
+
+#[test]
+fn captions_are_composited_over_the_base_image() {
+    let base = gradient(240, 160);
+    let meme = compositor()
+        .compose(
+            &base,
+            &[Caption::top("TOP TEXT"), Caption::bottom("BOTTOM TEXT")],
+        )
+        .unwrap();
+    assert_eq!((meme.width(), meme.height()), (240, 160));
+
+    // White fill and black outline in the top and bottom bands only.
+    for band in [0..40, 120..160] {
+        assert!(rows_with(&meme, band.clone(), |pixel| pixel == [255; 4]));
+        assert!(rows_with(&meme, band, |pixel| pixel[..3]
+            .iter()
+            .all(|&c| c < 24)));
+    }
+    for y in 50..110 {
+        for x in 0..240 {
+            assert_eq!(meme.pixel(x, y), base.pixel(x, y), "pixel {x},{y}");
+        }
+    }
+}
+
+#[test]
+fn positioned_captions_and_png_output() {
+    let base = gradient(200, 200);
+    let compositor = compositor();
+    let png = compositor
+        .compose_png(
+            &base,
+            &[Caption::new("I", CaptionPosition::At(Vec2::new(0.25, 0.5)))],
+        )
+        .unwrap();
+    let meme = Image::decode_png(&png).unwrap();
+    assert_eq!(meme.pixel(50, 100), [255, 255, 255, 255]);
+    assert_eq!(meme.pixel(150, 100), base.pixel(150, 100));
+
+    // A single word wider than the image is shrunk rather than clipped.
+    let long = compositor
+        .compose(&base, &[Caption::top("SUPERCALIFRAGILISTIC")])
+        .unwrap();
+    assert_eq!(long.pixel(1, 20), base.pixel(1, 20));
+    assert_eq!(long.pixel(198, 20), base.pixel(198, 20));
+    assert!(rows_with(&long, 0..40, |pixel| pixel == [255; 4]));
+
+    assert!(compositor.compose(&Image::new(0, 0).unwrap(), &[]).is_err());
+}
@@ -0,0 +1,98 @@
+use glam::{Vec2, Vec3};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{BackendKind, Mesh, Renderer, Submesh};
+
+fn assert_faces_point_outward(mesh: &Mesh) {
+    // Front faces are clockwise when seen from outside, so the right-handed
+    // triangle normal points into the mesh.
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| mesh.positions[triangle[i] as usize]);
+        let normal = (b - a).cross(c - a);
+        if normal.length_squared() < 1.0e-10 {
+            continue;
+        }
+        let centroid = (a + b + c) / 3.0;
+        assert!(normal.dot(centroid) < 0.0, "{triangle:?} faces inward");
+    }
+}
+
+#[test]
+fn primitives_are_valid_and_consistently_wound() {
+    let cuboid = Mesh::cuboid(Vec3::new(1.0, 2.0, 3.0));
+    cuboid.validate().unwrap();
+    assert_eq!((cuboid.vertex_count(), cuboid.index_count()), (24, 36));
+    assert_eq!(
+        cuboid.bounds(),
+        Some((Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0)))
+    );
+    assert_faces_point_outward(&cuboid);
+
+    let sphere = Mesh::uv_sphere(0.5, 16, 8);
+    sphere.validate().unwrap();
+    assert_faces_point_outward(&sphere);
+
+    let quad = Mesh::quad(Vec2::new(2.0, 1.0));
+    quad.validate().unwrap();
+    assert_eq!(quad.submesh_ranges(), vec![Submesh { start: 0, count: 6 }]);
+}
+
+#[test]
+fn validation_rejects_malformed_meshes() {
+    let triangle = Mesh::new(vec![Vec3::ZERO, Vec3::X, Vec3::Y], vec![0, 1, 2]);
+    triangle.validate().unwrap();
+
+    let out_of_range = Mesh::new(triangle.positions.clone(), vec![0, 1, 3]);
+    assert!(out_of_range.validate().is_err());
+
+    let mismatched = Mesh {
+        normals: vec![Vec3::Z],
+        ..triangle.clone()
+    };
+    assert!(mismatched.validate().is_err());
+
+    let bad_submesh = Mesh {
+        submeshes: vec![Submesh { start: 3, count: 3 }],
+        ..triangle
+    };
+    assert!(bad_submesh.validate().is_err());
+}
+
+#[test]
+fn asset_handles_are_generational() {
+    let mut meshes = Assets::new();
+    let first = meshes.insert(Mesh::quad(Vec2::ONE));
+    let revision = meshes.revision(first).unwrap();
+    meshes.get_mut(first).unwrap().colors.clear();
+    assert!(meshes.revision(first).unwrap() > revision);
+
+    assert!(meshes.remove(first).is_some());
+    let second = meshes.insert(Mesh::quad(Vec2::ONE));
+    assert_eq!(first.index(), second.index());
+    assert!(!meshes.contains(first));
+    assert!(meshes.get(first).is_none());
+    assert!(meshes.contains(second));
+    assert_eq!(meshes.len(), 1);
+}
+
+#[test]
+fn renderer_uploads_only_changed_meshes() {
+    let mut renderer = Renderer::offscreen(32, 32, &[BackendKind::Software]).unwrap();
+    let mut meshes = Assets::new();
+    let cube = meshes.insert(Mesh::cuboid(Vec3::ONE));
+    let sphere = meshes.insert(Mesh::uv_sphere(1.0, 8, 4));
+
+    assert_eq!(renderer.sync_meshes(&meshes).unwrap(), 2);
+    assert_eq!(renderer.sync_meshes(&meshes).unwrap(), 0);
+
+    meshes.get_mut(cube).unwrap().positions[0] = Vec3::ZERO;
+    assert_eq!(renderer.sync_meshes(&meshes).unwrap(), 1);
+
+    meshes.remove(sphere);
+    assert_eq!(renderer.sync_meshes(&meshes).unwrap(), 0);
+    assert!(!renderer.is_mesh_uploaded(sphere));
+    assert!(renderer.is_mesh_uploaded(cube));
+
+    let broken = meshes.insert(Mesh::new(vec![Vec3::ZERO], vec![0, 1, 2]));
+    assert!(renderer.sync_meshes(&meshes).is_err());
+    assert!(!renderer.is_mesh_uploaded(broken));
+}
@@ -0,0 +1,308 @@
+use glam::Vec3;
+use meme_engine::physics::{
+    pull_body_poses, push_kinematic_targets, BodyDesc, BodyKind, ColliderDesc, ColliderShape,
+    PhysicsConfig, PhysicsEvent, PhysicsWorld, Pose, RigidBody,
+};
+use meme_engine::scene::{Registry, Transform};
+use meme_engine::{Engine, EngineConfig, EngineContext, EngineEvent, HeadlessConfig};
+use std::sync::{Arc, Mutex};
+
+fn step(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step(1.0 / 60.0);
+    }
+}
+
+#[test]
+fn dynamic_sphere_comes_to_rest_on_fixed_ground() {
+    let mut world = PhysicsWorld::new();
+    let ground = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    world
+        .add_collider(ground, &ColliderDesc::cuboid(Vec3::new(5.0, 0.5, 5.0)))
+        .unwrap();
+    let ball = world.create_body(&BodyDesc::dynamic(Vec3::new(0.0, 3.0, 0.0)));
+    world
+        .add_collider(ball, &ColliderDesc::sphere(0.5))
+        .unwrap();
+
+    step(&mut world, 240);
+
+    let pose = world.body_pose(ball).unwrap();
+    assert!((pose.position.y - 1.0).abs() < 0.05, "{pose:?}");
+    assert_eq!(world.body_kind(ground), Some(BodyKind::Fixed));
+}
+
+#[test]
+fn kinematic_body_follows_its_target() {
+    let mut world = PhysicsWorld::new();
+    let platform = world.create_body(&BodyDesc::kinematic(Vec3::ZERO));
+    let target = Pose {
+        position: Vec3::new(2.0, 0.0, 0.0),
+        ..Pose::default()
+    };
+    world.set_kinematic_target(platform, target);
+    step(&mut world, 1);
+    let pose = world.body_pose(platform).unwrap();
+    assert!((pose.position - target.position).length() < 1e-4);
+}
+
+#[test]
+fn removing_a_body_removes_its_colliders() {
+    let mut world = PhysicsWorld::new();
+    let body = world.create_body(&BodyDesc::dynamic(Vec3::ZERO));
+    let first = world
+        .add_collider(body, &ColliderDesc::sphere(0.5))
+        .unwrap();
+    world
+        .add_collider(body, &ColliderDesc::capsule(0.5, 0.25))
+        .unwrap();
+    assert_eq!(world.body_colliders(body).len(), 2);
+    assert_eq!(world.collider_body(first), Some(body));
+
+    assert!(world.remove_body(body));
+    assert!(!world.contains_body(body));
+    assert_eq!(world.collider_count(), 0);
+    assert!(world
+        .add_collider(body, &ColliderDesc::sphere(1.0))
+        .is_err());
+}
+
+#[test]
+fn rejects_invalid_mesh_colliders() {
+    let mut world = PhysicsWorld::new();
+    let body = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    let mesh = ColliderDesc::new(ColliderShape::TriMesh {
+        vertices: vec![Vec3::ZERO, Vec3::X, Vec3::Y],
+        indices: vec![[0, 1, 3]],
+    });
+    assert!(world.add_collider(body, &mesh).is_err());
+    let hull = ColliderDesc::new(ColliderShape::ConvexHull {
+        points: vec![Vec3::ZERO, Vec3::X],
+    });
+    assert!(world.add_collider(body, &hull).is_err());
+}
+
+#[test]
+fn sync_moves_transforms_and_kinematic_bodies() {
+    let mut world = PhysicsWorld::new();
+    let mut registry = Registry::new();
+    let falling = world.create_body(&BodyDesc::dynamic(Vec3::new(0.0, 5.0, 0.0)));
+    world
+        .add_collider(falling, &ColliderDesc::sphere(0.5))
+        .unwrap();
+    let platform = world.create_body(&BodyDesc::kinematic(Vec3::ZERO));
+    let falling_entity = registry.spawn((Transform::default(), RigidBody(falling)));
+    let platform_entity = registry.spawn((
+        Transform::from_position(Vec3::new(0.0, 0.0, 3.0)),
+        RigidBody(platform),
+    ));
+
+    push_kinematic_targets(&mut world, &registry);
+    world.step(1.0 / 60.0);
+    pull_body_poses(&world, &mut registry);
+
+    let falling_transform = registry.get::<Transform>(falling_entity).unwrap();
+    assert!(falling_transform.position.y < 5.0 && falling_transform.position.y > 4.9);
+    let platform_pose = world.body_pose(platform).unwrap();
+    assert!((platform_pose.position - Vec3::new(0.0, 0.0, 3.0)).length() < 1e-4);
+    assert_eq!(
+        registry.get::<Transform>(platform_entity).unwrap().position,
+        Vec3::new(0.0, 0.0, 3.0)
+    );
+}
+
+#[test]
+fn reports_collision_and_sensor_events() {
+    let mut world = PhysicsWorld::new();
+    let ground = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    let ground_collider = world
+        .add_collider(ground, &ColliderDesc::cuboid(Vec3::new(5.0, 0.5, 5.0)))
+        .unwrap();
+    let trigger = world
+        .add_collider(
+            ground,
+            &ColliderDesc {
+                sensor: true,
+                offset: Pose {
+                    position: Vec3::new(0.0, 2.0, 0.0),
+                    ..Pose::default()
+                },
+                ..ColliderDesc::cuboid(Vec3::splat(0.5))
+            },
+        )
+        .unwrap();
+    let ball = world.create_body(&BodyDesc::dynamic(Vec3::new(0.0, 4.0, 0.0)));
+    let ball_collider = world
+        .add_collider(
+            ball,
+            &ColliderDesc {
+                contact_force_threshold: Some(0.0),
+                ..ColliderDesc::sphere(0.25)
+            },
+        )
+        .unwrap();
+
+    let mut seen = Vec::new();
+    for _ in 0..180 {
+        world.step(1.0 / 60.0);
+        seen.extend(world.events().copied());
+    }
+
+    assert!(seen.contains(&PhysicsEvent::SensorEntered {
+        sensor: trigger,
+        other: ball_collider,
+    }));
+    assert!(seen.iter().any(|event| matches!(
+        event,
+        PhysicsEvent::SensorExited { sensor, removed: false, .. } if *sensor == trigger
+    )));
+    assert!(seen.iter().any(
+        |event| matches!(event, PhysicsEvent::CollisionStarted { .. })
+            && event.involves(ground_collider)
+            && event.involves(ball_collider)
+    ));
+    assert!(seen
+        .iter()
+        .any(|event| matches!(event, PhysicsEvent::ContactForce { .. })));
+}
+
+#[test]
+fn removing_a_sensor_reports_it_as_the_sensor() {
+    let mut world = PhysicsWorld::with_config(PhysicsConfig {
+        gravity: Vec3::ZERO,
+        ..PhysicsConfig::default()
+    });
+    // The ball is added first so rapier pairs it as `a` and the sensor as `b`.
+    let ball = world.create_body(&BodyDesc::dynamic(Vec3::ZERO));
+    let ball_collider = world
+        .add_collider(ball, &ColliderDesc::sphere(0.5))
+        .unwrap();
+    let zone = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    let trigger = world
+        .add_collider(
+            zone,
+            &ColliderDesc {
+                sensor: true,
+                ..ColliderDesc::cuboid(Vec3::splat(2.0))
+            },
+        )
+        .unwrap();
+    step(&mut world, 1);
+    assert!(world.events().any(|event| *event
+        == PhysicsEvent::SensorEntered {
+            sensor: trigger,
+            other: ball_collider,
+        }));
+
+    assert!(world.remove_collider(trigger));
+    step(&mut world, 1);
+    let events: Vec<PhysicsEvent> = world.events().copied().collect();
+    assert_eq!(
+        events,
+        [PhysicsEvent::SensorExited {
+            sensor: trigger,
+            other: ball_collider,
+            removed: true,
+        }]
+    );
+}
+
+#[test]
+fn config_controls_gravity_ccd_and_sleeping() {
+    let moon = PhysicsConfig {
+        gravity: Vec3::new(0.0, -1.62, 0.0),
+        substeps: 4,
+        ..PhysicsConfig::default()
+    };
+    let mut world = PhysicsWorld::with_config(moon);
+    let ball = world.create_body(&BodyDesc::dynamic(Vec3::ZERO));
+    world
+        .add_collider(ball, &ColliderDesc::sphere(0.5))
+        .unwrap();
+    step(&mut world, 60);
+    let velocity = world.linear_velocity(ball).unwrap();
+    assert!((velocity.y + 1.62).abs() < 1e-3, "{velocity}");
+
+    world.set_gravity(Vec3::ZERO);
+    world.set_ccd_enabled(true);
+    world.set_sleep_thresholds(10.0, 10.0, 0.1);
+    assert_eq!(world.config().gravity, Vec3::ZERO);
+    assert_eq!(world.config().substeps, 4);
+    step(&mut world, 30);
+    assert!(world.is_sleeping(ball));
+
+    // Turning the global default off keeps CCD on bodies that asked for it.
+    let bullet = world.create_body(&BodyDesc {
+        ccd: true,
+        ..BodyDesc::dynamic(Vec3::ZERO)
+    });
+    assert!(world.is_ccd_enabled(ball) && world.is_ccd_enabled(bullet));
+    world.set_ccd_enabled(false);
+    assert!(!world.is_ccd_enabled(ball));
+    assert!(world.is_ccd_enabled(bullet));
+}
+
+#[test]
+fn frames_see_events_from_every_fixed_step() {
+    let config = EngineConfig {
+        target_fps: 30,
+        fixed_update_hz: 60,
+        headless: Some(HeadlessConfig {
+            max_frames: Some(2),
+            realtime: false,
+        }),
+        physics: PhysicsConfig {
+            gravity: Vec3::ZERO,
+            ..PhysicsConfig::default()
+        },
+        ..EngineConfig::default()
+    };
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let recorded = frames.clone();
+    let entries = Arc::new(Mutex::new(0));
+    let step_entries = entries.clone();
+    Engine::new(config)
+        .unwrap()
+        .run_with(
+            move |event: &EngineEvent, ctx: &mut EngineContext<'_>| match event {
+                EngineEvent::Startup => {
+                    // The ball starts inside the sensor, so the first fixed step of
+                    // the first frame reports the overlap and the second reports nothing.
+                    let zone = ctx.physics.create_body(&BodyDesc::fixed(Vec3::ZERO));
+                    ctx.physics
+                        .add_collider(
+                            zone,
+                            &ColliderDesc {
+                                sensor: true,
+                                ..ColliderDesc::cuboid(Vec3::splat(2.0))
+                            },
+                        )
+                        .unwrap();
+                    let ball = ctx.physics.create_body(&BodyDesc::dynamic(Vec3::ZERO));
+                    ctx.physics
+                        .add_collider(ball, &ColliderDesc::sphere(0.5))
+                        .unwrap();
+                }
+                EngineEvent::FixedUpdate { .. } => {
+                    // Each step only reports its own events.
+                    *step_entries.lock().unwrap() += ctx
+                        .physics
+                        .events()
+                        .filter(|event| matches!(event, PhysicsEvent::SensorEntered { .. }))
+                        .count();
+                }
+                EngineEvent::Frame { .. } => {
+                    recorded.lock().unwrap().push(ctx.physics_events.to_vec());
+                }
+                _ => {}
+            },
+        )
+        .unwrap();
+
+    assert_eq!(*entries.lock().unwrap(), 1);
+    let frames = frames.lock().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].len(), 1);
+    assert!(matches!(frames[0][0], PhysicsEvent::SensorEntered { .. }));
+    assert!(frames[1].is_empty());
+}
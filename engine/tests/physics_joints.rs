@@ -0,0 +1,104 @@
+use glam::Vec3;
+use meme_engine::physics::{
+    BodyDesc, BodyHandle, ColliderDesc, JointDesc, JointMotor, PhysicsWorld,
+};
+
+fn anchored_ball(world: &mut PhysicsWorld, position: Vec3) -> (BodyHandle, BodyHandle) {
+    let anchor = world.create_body(&BodyDesc::fixed(Vec3::new(0.0, 5.0, 0.0)));
+    let ball = world.create_body(&BodyDesc::dynamic(position));
+    world
+        .add_collider(ball, &ColliderDesc::sphere(0.25))
+        .unwrap();
+    (anchor, ball)
+}
+
+fn step(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step(1.0 / 60.0);
+    }
+}
+
+#[test]
+fn revolute_joint_keeps_pendulum_at_its_length() {
+    let mut world = PhysicsWorld::new();
+    let (anchor, ball) = anchored_ball(&mut world, Vec3::new(2.0, 5.0, 0.0));
+    let hinge = JointDesc::revolute(Vec3::Z).with_anchors(Vec3::ZERO, Vec3::new(-2.0, 0.0, 0.0));
+    let joint = world.create_joint(anchor, ball, &hinge).unwrap();
+    assert_eq!(world.joint_bodies(joint), Some((anchor, ball)));
+
+    step(&mut world, 90);
+
+    let position = world.body_pose(ball).unwrap().position;
+    assert!(
+        position.distance(Vec3::new(2.0, 5.0, 0.0)) > 1.0,
+        "{position}"
+    );
+    assert!((position.distance(Vec3::new(0.0, 5.0, 0.0)) - 2.0).abs() < 0.05);
+    assert!(position.z.abs() < 1e-3);
+}
+
+#[test]
+fn revolute_motor_drives_angular_velocity() {
+    let mut world = PhysicsWorld::new();
+    let (anchor, wheel) = anchored_ball(&mut world, Vec3::new(0.0, 5.0, 0.0));
+    let desc = JointDesc {
+        motor: Some(JointMotor::velocity(3.0, 100.0)),
+        ..JointDesc::revolute(Vec3::Y)
+    };
+    let joint = world.create_joint(anchor, wheel, &desc).unwrap();
+    step(&mut world, 60);
+    let spin = world.angular_velocity(wheel).unwrap();
+    assert!((spin.y - 3.0).abs() < 0.1, "{spin}");
+
+    assert!(world.set_joint_motor(joint, None));
+    world.set_angular_velocity(wheel, Vec3::ZERO);
+    step(&mut world, 10);
+    assert!(world.angular_velocity(wheel).unwrap().length() < 1e-3);
+}
+
+#[test]
+fn prismatic_limits_and_rope_length_are_enforced() {
+    let mut world = PhysicsWorld::new();
+    let (anchor, slider) = anchored_ball(&mut world, Vec3::new(0.0, 5.0, 0.0));
+    let desc = JointDesc {
+        limits: Some([0.0, 1.0]),
+        ..JointDesc::prismatic(Vec3::X)
+    };
+    world.create_joint(anchor, slider, &desc).unwrap();
+    world.set_linear_velocity(slider, Vec3::new(10.0, 0.0, 0.0));
+
+    let (rope_anchor, weight) = anchored_ball(&mut world, Vec3::new(5.0, 5.0, 0.0));
+    world
+        .create_joint(rope_anchor, weight, &JointDesc::rope(3.0))
+        .unwrap();
+
+    step(&mut world, 120);
+
+    let slider_position = world.body_pose(slider).unwrap().position;
+    assert!(
+        slider_position.x <= 1.05 && slider_position.x > 0.5,
+        "{slider_position}"
+    );
+    assert!((slider_position.y - 5.0).abs() < 1e-2);
+    let weight_position = world.body_pose(weight).unwrap().position;
+    assert!(weight_position.distance(Vec3::new(0.0, 5.0, 0.0)) <= 3.05);
+}
+
+#[test]
+fn joints_are_removed_with_their_bodies() {
+    let mut world = PhysicsWorld::new();
+    let (anchor, ball) = anchored_ball(&mut world, Vec3::new(0.0, 4.0, 0.0));
+    let joint = world
+        .create_joint(anchor, ball, &JointDesc::spring(1.0, 50.0, 1.0))
+        .unwrap();
+    assert!(world
+        .create_joint(anchor, ball, &JointDesc::revolute(Vec3::ZERO))
+        .is_err());
+
+    world.remove_body(ball);
+    assert!(!world.contains_joint(joint));
+    assert_eq!(world.joint_count(), 0);
+    assert!(world
+        .create_joint(anchor, ball, &JointDesc::fixed())
+        .is_err());
+}
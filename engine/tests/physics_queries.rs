@@ -0,0 +1,147 @@
+use glam::Vec3;
+use meme_engine::physics::{
+    BodyDesc, ColliderDesc, ColliderHandle, ColliderShape, CollisionGroups, PhysicsWorld, Pose,
+    QueryFilter,
+};
+
+fn stacked_world() -> (PhysicsWorld, Vec<ColliderHandle>) {
+    let mut world = PhysicsWorld::new();
+    let mut colliders = Vec::new();
+    for (index, height) in [0.0, 3.0].into_iter().enumerate() {
+        let body = world.create_body(&BodyDesc::fixed(Vec3::new(0.0, height, 0.0)));
+        let desc = ColliderDesc {
+            groups: CollisionGroups::new(1 << index, u32::MAX),
+            ..ColliderDesc::cuboid(Vec3::new(2.0, 0.5, 2.0))
+        };
+        colliders.push(world.add_collider(body, &desc).unwrap());
+    }
+    world.update_queries();
+    (world, colliders)
+}
+
+#[test]
+fn raycast_hits_nearest_collider_and_respects_filters() {
+    let (world, colliders) = stacked_world();
+    let origin = Vec3::new(0.0, 10.0, 0.0);
+
+    let hit = world
+        .raycast(origin, Vec3::NEG_Y, 100.0, &QueryFilter::default())
+        .unwrap();
+    assert_eq!(hit.collider, colliders[1]);
+    assert!((hit.distance - 6.5).abs() < 1e-4);
+    assert!((hit.point - Vec3::new(0.0, 3.5, 0.0)).length() < 1e-4);
+    assert!((hit.normal - Vec3::Y).length() < 1e-4);
+
+    let skip_top = QueryFilter::excluding_body(hit.body.unwrap());
+    let hit = world
+        .raycast(origin, Vec3::NEG_Y, 100.0, &skip_top)
+        .unwrap();
+    assert_eq!(hit.collider, colliders[0]);
+
+    let ground_only = QueryFilter {
+        groups: Some(CollisionGroups::new(u32::MAX, 1)),
+        ..QueryFilter::default()
+    };
+    let hit = world
+        .raycast(origin, Vec3::NEG_Y, 100.0, &ground_only)
+        .unwrap();
+    assert_eq!(hit.collider, colliders[0]);
+
+    assert!(world
+        .raycast(origin, Vec3::NEG_Y, 5.0, &QueryFilter::default())
+        .is_none());
+}
+
+#[test]
+fn raycast_all_returns_hits_sorted_by_distance() {
+    let (world, colliders) = stacked_world();
+    let hits = world.raycast_all(
+        Vec3::new(0.0, 10.0, 0.0),
+        Vec3::NEG_Y,
+        100.0,
+        &QueryFilter::default(),
+    );
+    let order: Vec<_> = hits.iter().map(|hit| hit.collider).collect();
+    assert_eq!(order, vec![colliders[1], colliders[0]]);
+}
+
+#[test]
+fn shape_cast_point_projection_and_overlaps() {
+    let (world, colliders) = stacked_world();
+    let filter = QueryFilter::default();
+
+    let hit = world
+        .shape_cast(
+            &ColliderShape::Sphere { radius: 0.5 },
+            Pose {
+                position: Vec3::new(0.0, 2.0, 0.0),
+                ..Pose::default()
+            },
+            Vec3::NEG_Y,
+            10.0,
+            &filter,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(hit.collider, colliders[0]);
+    assert!((hit.distance - 1.0).abs() < 1e-3);
+    assert!(!hit.penetrating);
+
+    let projection = world
+        .project_point(Vec3::new(0.0, 1.5, 0.0), &filter)
+        .unwrap();
+    assert_eq!(projection.collider, colliders[0]);
+    assert!((projection.point.y - 0.5).abs() < 1e-4);
+    assert!(!projection.inside);
+
+    let overlaps = world.overlap_aabb(
+        Vec3::new(-1.0, 2.0, -1.0),
+        Vec3::new(1.0, 4.0, 1.0),
+        &filter,
+    );
+    assert_eq!(overlaps, vec![colliders[1]]);
+    let overlaps = world
+        .overlap_shape(
+            &ColliderShape::Sphere { radius: 2.0 },
+            Pose {
+                position: Vec3::new(0.0, 1.5, 0.0),
+                ..Pose::default()
+            },
+            &filter,
+        )
+        .unwrap();
+    assert_eq!(overlaps.len(), 2);
+}
+
+#[test]
+fn queries_see_changes_made_since_the_last_step() {
+    let mut world = PhysicsWorld::new();
+    let origin = Vec3::new(0.0, 10.0, 0.0);
+    let filter = QueryFilter::default();
+    let body = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    let collider = world
+        .add_collider(body, &ColliderDesc::cuboid(Vec3::splat(0.5)))
+        .unwrap();
+    let hit = world.raycast(origin, Vec3::NEG_Y, 100.0, &filter).unwrap();
+    assert_eq!(hit.collider, collider);
+    assert!((hit.distance - 9.5).abs() < 1e-4);
+
+    world.set_body_pose(
+        body,
+        Pose {
+            position: Vec3::new(0.0, 5.0, 0.0),
+            ..Pose::default()
+        },
+    );
+    let hit = world.raycast(origin, Vec3::NEG_Y, 100.0, &filter).unwrap();
+    assert!((hit.distance - 4.5).abs() < 1e-4);
+
+    world.remove_collider(collider);
+    assert!(world.raycast(origin, Vec3::NEG_Y, 100.0, &filter).is_none());
+}
+
+#[test]
+fn worlds_can_be_queried_from_other_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<PhysicsWorld>();
+}
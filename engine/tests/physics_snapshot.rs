@@ -0,0 +1,82 @@
+use glam::Vec3;
+use meme_engine::physics::{
+    BodyDesc, BodyHandle, ColliderDesc, PhysicsConfig, PhysicsSnapshot, PhysicsWorld,
+};
+
+fn tumbling_world() -> (PhysicsWorld, Vec<BodyHandle>) {
+    let mut world = PhysicsWorld::new();
+    let ground = world.create_body(&BodyDesc::fixed(Vec3::ZERO));
+    world
+        .add_collider(ground, &ColliderDesc::cuboid(Vec3::new(10.0, 0.5, 10.0)))
+        .unwrap();
+    let boxes = (0..4)
+        .map(|index| {
+            let body = world.create_body(&BodyDesc {
+                angular_velocity: Vec3::new(1.0, 2.0, index as f32),
+                ..BodyDesc::dynamic(Vec3::new(index as f32 * 0.3, 2.0 + index as f32, 0.0))
+            });
+            world
+                .add_collider(body, &ColliderDesc::cuboid(Vec3::splat(0.4)))
+                .unwrap();
+            body
+        })
+        .collect();
+    (world, boxes)
+}
+
+fn run(world: &mut PhysicsWorld, bodies: &[BodyHandle], steps: usize) -> Vec<Vec3> {
+    for _ in 0..steps {
+        world.step(1.0 / 60.0);
+    }
+    bodies
+        .iter()
+        .map(|&body| world.body_pose(body).unwrap().position)
+        .collect()
+}
+
+#[test]
+fn restoring_a_snapshot_replays_identically() {
+    let (mut world, bodies) = tumbling_world();
+    run(&mut world, &bodies, 20);
+    let snapshot = world.snapshot();
+
+    let first = run(&mut world, &bodies, 60);
+    world.restore(&snapshot);
+    let second = run(&mut world, &bodies, 60);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn snapshots_round_trip_through_bytes() {
+    let (mut world, bodies) = tumbling_world();
+    run(&mut world, &bodies, 20);
+    let bytes = world.snapshot().to_bytes().unwrap();
+    let expected = run(&mut world, &bodies, 30);
+
+    let (mut restored, _) = tumbling_world();
+    let snapshot = PhysicsSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(snapshot.body_count(), 5);
+    restored.restore(&snapshot);
+    assert_eq!(run(&mut restored, &bodies, 30), expected);
+
+    assert!(PhysicsSnapshot::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+}
+
+#[test]
+fn snapshots_carry_gravity_and_solver_settings() {
+    let (mut world, bodies) = tumbling_world();
+    world.set_config(PhysicsConfig {
+        gravity: Vec3::new(0.0, -1.62, 0.0),
+        solver_iterations: 8,
+        substeps: 2,
+        ..PhysicsConfig::default()
+    });
+    run(&mut world, &bodies, 10);
+    let bytes = world.snapshot().to_bytes().unwrap();
+    let expected = run(&mut world, &bodies, 30);
+
+    let (mut restored, _) = tumbling_world();
+    restored.restore(&PhysicsSnapshot::from_bytes(&bytes).unwrap());
+    assert_eq!(restored.config(), world.config());
+    assert_eq!(run(&mut restored, &bodies, 30), expected);
+}
@@ -0,0 +1,159 @@
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{
+    collect_sprite_batches, BackendKind, FilterMode, Image, RenderFrame, Renderer, Sampler, Sprite,
+    SpriteBatcher, SpriteSpace, UvRect, WrapMode,
+};
+use meme_engine::scene::{Scene, Transform};
+
+const RED: Vec4 = Vec4::new(1.0, 0.0, 0.0, 1.0);
+const GREEN: Vec4 = Vec4::new(0.0, 1.0, 0.0, 1.0);
+
+fn at(x: f32, y: f32) -> Mat4 {
+    Mat4::from_translation(Vec3::new(x, y, 0.0))
+}
+
+#[test]
+fn thousands_of_sprites_collapse_into_few_batches() {
+    let mut textures: Assets<Image> = Assets::new();
+    let atlas = textures.insert(Image::new(64, 64).unwrap());
+    let other = textures.insert(Image::new(8, 8).unwrap());
+
+    let mut batcher = SpriteBatcher::new();
+    for index in 0..2000 {
+        let cell = UvRect::grid_cell(index % 4, index / 4 % 4, 4, 4);
+        let sprite = Sprite::new(atlas, Vec2::splat(4.0)).with_uv(cell);
+        batcher.push(sprite, at(index as f32, 0.0));
+        if index % 100 == 0 {
+            batcher.push(Sprite::new(other, Vec2::ONE), at(0.0, 0.0));
+        }
+    }
+    batcher.push(
+        Sprite::new(other, Vec2::ONE).with_z_order(1),
+        Mat4::IDENTITY,
+    );
+    batcher.push(Sprite::new(atlas, Vec2::ONE).in_world(), Mat4::IDENTITY);
+    batcher.push(
+        Sprite {
+            visible: false,
+            ..Sprite::new(atlas, Vec2::ONE)
+        },
+        Mat4::IDENTITY,
+    );
+    assert_eq!(batcher.len(), 2000 + 20 + 2);
+
+    let batches = batcher.build(Mat4::IDENTITY);
+    let summary: Vec<(SpriteSpace, usize)> = batches
+        .iter()
+        .map(|batch| (batch.space, batch.sprite_count()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (SpriteSpace::World, 1),
+            (SpriteSpace::Screen, 2000),
+            (SpriteSpace::Screen, 21),
+        ]
+    );
+    assert_eq!(batches[1].texture, Some(atlas));
+    assert_eq!(batches[1].indices.len(), 2000 * 6);
+}
+
+#[test]
+fn sprite_quads_respect_pivot_rotation_and_atlas_uvs() {
+    let uv = UvRect::from_pixels(16, 0, 16, 32, (64, 32));
+    assert_eq!(uv.min, Vec2::new(0.25, 0.0));
+    assert_eq!(uv.max, Vec2::new(0.5, 1.0));
+
+    let sprite = Sprite::solid(RED, Vec2::new(10.0, 20.0))
+        .with_pivot(Vec2::ZERO)
+        .with_uv(uv);
+    let rotated = Mat4::from_rotation_translation(
+        Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+        Vec3::new(5.0, 5.0, 0.0),
+    );
+    let mut batcher = SpriteBatcher::new();
+    batcher.push(sprite, rotated);
+    let batch = &batcher.build(Mat4::IDENTITY)[0];
+
+    let corners: Vec<Vec3> = batch.vertices.iter().map(|v| v.position).collect();
+    let expected = [
+        Vec3::new(5.0, 5.0, 0.0),
+        Vec3::new(5.0, 15.0, 0.0),
+        Vec3::new(-15.0, 15.0, 0.0),
+        Vec3::new(-15.0, 5.0, 0.0),
+    ];
+    for (corner, expected) in corners.iter().zip(expected) {
+        assert!(
+            corner.abs_diff_eq(expected, 1.0e-5),
+            "{corner} != {expected}"
+        );
+    }
+    assert_eq!(batch.vertices[0].uv, uv.min);
+    assert_eq!(batch.vertices[2].uv, uv.max);
+    assert!(batch.vertices.iter().all(|v| v.color == RED));
+}
+
+#[test]
+fn software_renderer_draws_screen_and_world_sprites() {
+    let mut textures = Assets::new();
+    let mut atlas = Image::new(2, 1).unwrap();
+    atlas.set_pixel(0, 0, [0, 0, 255, 255]);
+    atlas.set_pixel(1, 0, [255, 255, 0, 255]);
+    let atlas = textures.insert(atlas);
+
+    let mut batcher = SpriteBatcher::new();
+    batcher.push(
+        Sprite::solid(GREEN, Vec2::splat(16.0)).with_z_order(1),
+        at(20.0, 20.0),
+    );
+    batcher.push(Sprite::solid(RED, Vec2::splat(16.0)), at(12.0, 12.0));
+    batcher.push(
+        Sprite {
+            sampler: Sampler::new(FilterMode::Nearest, WrapMode::Clamp),
+            ..Sprite::new(atlas, Vec2::splat(8.0)).with_uv(UvRect::grid_cell(1, 0, 2, 1))
+        },
+        at(52.0, 52.0),
+    );
+    batcher.push(
+        Sprite::solid(Vec4::new(1.0, 1.0, 1.0, 0.5), Vec2::splat(0.5)).in_world(),
+        Mat4::IDENTITY,
+    );
+    let view_projection = Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, -1.0, 1.0);
+    let frame = RenderFrame {
+        clear_color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        view_projection,
+        sprites: batcher.build(view_projection),
+        ..RenderFrame::default()
+    };
+
+    let mut renderer = Renderer::offscreen(64, 64, &[BackendKind::Software]).unwrap();
+    renderer.sync_textures(&textures).unwrap();
+    renderer.render(&frame).unwrap();
+    let image = renderer.capture_frame().unwrap();
+
+    assert_eq!(image.pixel(8, 8), [255, 0, 0, 255]);
+    assert_eq!(image.pixel(16, 16), [0, 255, 0, 255]);
+    assert_eq!(image.pixel(52, 52), [255, 255, 0, 255]);
+    // The world sprite spans the central quarter of the target at half opacity.
+    assert_eq!(image.pixel(32, 34), [128, 128, 128, 255]);
+    assert_eq!(image.pixel(32, 50), [0, 0, 0, 255]);
+}
+
+#[test]
+fn scene_sprites_follow_the_hierarchy() {
+    let mut scene = Scene::default();
+    let parent = scene
+        .registry
+        .spawn((Transform::from_position(Vec3::new(100.0, 50.0, 0.0)),));
+    let child = scene.registry.spawn((
+        Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),
+        Sprite::solid(RED, Vec2::splat(2.0)),
+    ));
+    scene.registry.set_parent(child, Some(parent)).unwrap();
+
+    let batches = collect_sprite_batches(&scene, Mat4::IDENTITY);
+    assert_eq!(batches.len(), 1);
+    let center = batches[0].vertices.iter().map(|v| v.position).sum::<Vec3>() / 4.0;
+    assert_eq!(center, Vec3::new(110.0, 50.0, 0.0));
+}
@@ -0,0 +1,164 @@
+use glam::{Mat4, Vec3, Vec4};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{BackendKind, DrawItem, Image, Mesh, RenderFrame, Renderer};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance {
+    pub per_channel: u8,
+    pub max_mismatched_fraction: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            per_channel: 2,
+            max_mismatched_fraction: 0.001,
+        }
+    }
+}
+
+pub fn render_offscreen(width: u32, height: u32, frame: &RenderFrame) -> Image {
+    render_scene(width, height, &Assets::new(), frame)
+}
+
+pub fn render_scene(width: u32, height: u32, meshes: &Assets<Mesh>, frame: &RenderFrame) -> Image {
+    let mut renderer =
+        Renderer::offscreen(width, height, &[BackendKind::Software]).expect("software renderer");
+    renderer.sync_meshes(meshes).expect("mesh upload");
+    renderer.render(frame).expect("render");
+    renderer.capture_frame().expect("capture")
+}
+
+pub fn vertex_colored_cube() -> Mesh {
+    let corners = [
+        ([-1.0, -1.0, -1.0], [1.0, 0.0, 0.0]),
+        ([1.0, -1.0, -1.0], [0.0, 1.0, 0.0]),
+        ([1.0, 1.0, -1.0], [0.0, 0.0, 1.0]),
+        ([-1.0, 1.0, -1.0], [1.0, 1.0, 0.0]),
+        ([-1.0, -1.0, 1.0], [1.0, 0.0, 1.0]),
+        ([1.0, -1.0, 1.0], [0.0, 1.0, 1.0]),
+        ([1.0, 1.0, 1.0], [1.0, 1.0, 1.0]),
+        ([-1.0, 1.0, 1.0], [0.1, 0.6, 0.9]),
+    ];
+    Mesh {
+        positions: corners.iter().map(|(p, _)| Vec3::from(*p)).collect(),
+        colors: corners
+            .iter()
+            .map(|(_, c)| Vec3::from(*c).extend(1.0))
+            .collect(),
+        indices: vec![
+            0, 1, 2, 0, 2, 3, 4, 6, 5, 4, 7, 6, 4, 5, 1, 4, 1, 0, 3, 2, 6, 3, 6, 7, 1, 5, 6, 1, 6,
+            2, 4, 0, 3, 4, 3, 7,
+        ],
+        ..Mesh::default()
+    }
+}
+
+// The camera and spin the reference images were originally rendered with.
+pub fn spinning_cube_frame(
+    meshes: &mut Assets<Mesh>,
+    clear_color: Vec4,
+    time_seconds: f32,
+    width: u32,
+    height: u32,
+) -> RenderFrame {
+    let aspect = width as f32 / height as f32;
+    let projection = Mat4::perspective_rh(45.0_f32.to_radians(), aspect, 0.1, 100.0);
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO, Vec3::Y);
+    let rotation =
+        Mat4::from_rotation_y(time_seconds * 0.8) * Mat4::from_rotation_x(time_seconds * 0.6);
+    let cube = meshes.insert(vertex_colored_cube());
+    RenderFrame {
+        clear_color,
+        time_seconds,
+        view_projection: projection * view,
+        draw_items: vec![DrawItem::new(cube, rotation)],
+        ..RenderFrame::default()
+    }
+}
+
+pub fn assert_golden(name: &str, actual: &Image, tolerance: Tolerance) {
+    let reference_path = golden_dir().join(format!("{name}.png"));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        actual
+            .save_png(&reference_path)
+            .expect("write reference image");
+        return;
+    }
+    let expected = Image::load_png(&reference_path).unwrap_or_else(|err| {
+        panic!(
+            "missing reference image {} ({err}); run with UPDATE_GOLDEN=1 to create it",
+            reference_path.display()
+        )
+    });
+    assert_images_match(name, &expected, actual, tolerance);
+}
+
+pub fn assert_images_match(name: &str, expected: &Image, actual: &Image, tolerance: Tolerance) {
+    if (expected.width(), expected.height()) != (actual.width(), actual.height()) {
+        let actual_path = write_output(name, "actual", actual);
+        panic!(
+            "{name}: size {}x{} does not match reference {}x{}; actual written to {}",
+            actual.width(),
+            actual.height(),
+            expected.width(),
+            expected.height(),
+            actual_path.display()
+        );
+    }
+
+    let (diff, mismatched) = diff_images(expected, actual, tolerance.per_channel);
+    let total = (actual.width() * actual.height()) as usize;
+    let allowed = (total as f32 * tolerance.max_mismatched_fraction).floor() as usize;
+    if mismatched > allowed {
+        let actual_path = write_output(name, "actual", actual);
+        let diff_path = write_output(name, "diff", &diff);
+        panic!(
+            "{name}: {mismatched} of {total} pixels differ by more than {} (allowed {allowed}); \
+             actual written to {}, diff written to {}",
+            tolerance.per_channel,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+fn diff_images(expected: &Image, actual: &Image, per_channel: u8) -> (Image, usize) {
+    let mut diff = Image::new(actual.width(), actual.height()).unwrap();
+    let mut mismatched = 0;
+    for y in 0..actual.height() {
+        for x in 0..actual.width() {
+            let a = expected.pixel(x, y);
+            let b = actual.pixel(x, y);
+            let differs = a
+                .iter()
+                .zip(b.iter())
+                .any(|(a, b)| a.abs_diff(*b) > per_channel);
+            let pixel = if differs {
+                mismatched += 1;
+                [255, 0, 255, 255]
+            } else {
+                let luma = ((b[0] as u32 * 3 + b[1] as u32 * 6 + b[2] as u32) / 10) as u8;
+                let dimmed = luma / 3;
+                [dimmed, dimmed, dimmed, 255]
+            };
+            diff.set_pixel(x, y, pixel);
+        }
+    }
+    (diff, mismatched)
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+fn write_output(name: &str, suffix: &str, image: &Image) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+    std::fs::create_dir_all(&dir).expect("create golden output dir");
+    let path = dir.join(format!("{name}.{suffix}.png"));
+    image.save_png(&path).expect("write golden output");
+    path
+}
@@ -0,0 +1,183 @@
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use meme_engine::assets::Assets;
+use meme_engine::renderer::{BackendKind, RenderFrame, Renderer, SpriteBatcher};
+use meme_engine::scene::{Scene, Transform};
+use meme_engine::text::{layout_text, Font, Text, TextAlign, TextRenderer, TextStyle};
+
+const FONT_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../assets/fonts/DejaVuSans-Bold.ttf"
+);
+
+fn font() -> Font {
+    Font::load(FONT_PATH).unwrap()
+}
+
+#[test]
+fn fonts_load_and_report_metrics() {
+    let font = font();
+    let metrics = font.line_metrics(32.0);
+    assert!(metrics.ascent > 0.0 && metrics.descent < 0.0);
+    // The pixel size spans ascent to descent.
+    assert!((metrics.ascent - metrics.descent - 32.0).abs() < 1.0e-3);
+    assert!(metrics.height() >= 32.0);
+    assert!(font.has_glyph('A'));
+    assert!(Font::from_bytes(b"not a font".to_vec()).is_err());
+    assert!(Font::load("missing.ttf").is_err());
+}
+
+#[test]
+fn layout_wraps_and_aligns_lines() {
+    let font = font();
+    let style = TextStyle::new(24.0).with_max_width(120.0);
+    let layout = layout_text(&font, "one does not simply render text", &style);
+    assert!(layout.line_count() > 1);
+    assert!(layout.line_widths.iter().all(|&width| width <= 120.0));
+    assert_eq!(layout.size.x, 120.0);
+
+    let two_lines = layout_text(&font, "WIDE LINE\nI", &TextStyle::new(24.0));
+    assert_eq!(two_lines.line_count(), 2);
+    let last = two_lines.glyphs.last().unwrap();
+    assert_eq!(last.character, 'I');
+    assert_eq!(last.position.x, 0.0);
+    assert!(last.position.y > two_lines.glyphs[0].position.y);
+
+    let centered = layout_text(
+        &font,
+        "WIDE LINE\nI",
+        &TextStyle::new(24.0).with_align(TextAlign::Center),
+    );
+    let i = centered.glyphs.last().unwrap().position.x;
+    assert!((i - (centered.size.x - centered.line_widths[1]) * 0.5).abs() < 1.0e-3);
+    let right = layout_text(
+        &font,
+        "WIDE LINE\nI",
+        &TextStyle::new(24.0).with_align(TextAlign::Right),
+    );
+    assert!(right.glyphs.last().unwrap().position.x > i);
+}
+
+#[test]
+fn glyphs_are_cached_in_a_shared_atlas() {
+    let mut fonts = Assets::new();
+    let mut textures = Assets::new();
+    let font = fonts.insert(font());
+    let caption = Text::new("TOP TEXT", font, TextStyle::meme(48.0));
+    let plain = Text::new("TOP", font, TextStyle::new(48.0));
+
+    let mut text_renderer = TextRenderer::new();
+    let mut batcher = SpriteBatcher::new();
+    text_renderer.queue(
+        &fonts,
+        &mut textures,
+        [(&caption, Mat4::IDENTITY), (&plain, Mat4::IDENTITY)],
+        &mut batcher,
+    );
+    // Seven visible glyphs with an outline each, plus three plain ones.
+    assert_eq!(batcher.len(), 7 * 2 + 3);
+    let batches = batcher.build(Mat4::IDENTITY);
+    assert_eq!(batches.len(), 1);
+    let atlas = text_renderer.atlas_texture().unwrap();
+    assert_eq!(batches[0].texture, Some(atlas));
+
+    let glyphs = text_renderer.cached_glyphs();
+    let revision = textures.revision(atlas).unwrap();
+    let mut batcher = SpriteBatcher::new();
+    text_renderer.queue(
+        &fonts,
+        &mut textures,
+        [(&caption, Mat4::IDENTITY)],
+        &mut batcher,
+    );
+    assert_eq!(text_renderer.cached_glyphs(), glyphs);
+    assert_eq!(textures.revision(atlas), Some(revision));
+
+    let bigger = Text::new("TOP", font, TextStyle::new(64.0));
+    text_renderer.queue(
+        &fonts,
+        &mut textures,
+        [(&bigger, Mat4::IDENTITY)],
+        &mut batcher,
+    );
+    assert!(textures.revision(atlas).unwrap() > revision);
+}
+
+#[test]
+fn glyphs_larger_than_the_atlas_are_skipped() {
+    let mut fonts = Assets::new();
+    let mut textures = Assets::new();
+    let font = fonts.insert(font());
+    let huge = Text::new("W", font, TextStyle::new(1400.0));
+    let small = Text::new("ok", font, TextStyle::new(24.0));
+
+    let mut text_renderer = TextRenderer::new();
+    let mut batcher = SpriteBatcher::new();
+    text_renderer.queue(
+        &fonts,
+        &mut textures,
+        [(&huge, Mat4::IDENTITY), (&small, Mat4::IDENTITY)],
+        &mut batcher,
+    );
+    assert_eq!(batcher.len(), 2);
+
+    // The oversized glyph is remembered, so the atlas isn't rebuilt next frame.
+    let atlas = text_renderer.atlas_texture().unwrap();
+    let revision = textures.revision(atlas).unwrap();
+    let mut batcher = SpriteBatcher::new();
+    text_renderer.queue(
+        &fonts,
+        &mut textures,
+        [(&huge, Mat4::IDENTITY), (&small, Mat4::IDENTITY)],
+        &mut batcher,
+    );
+    assert_eq!(batcher.len(), 2);
+    assert_eq!(textures.revision(atlas), Some(revision));
+}
+
+#[test]
+fn outlined_caption_renders_in_screen_and_world_space() {
+    let mut scene = Scene::default();
+    let font = scene.fonts.insert(font());
+    scene.registry.spawn((
+        Transform::from_position(Vec3::new(32.0, 32.0, 0.0)),
+        Text::new(
+            "I",
+            font,
+            TextStyle::meme(48.0).with_outline(4.0, Vec4::new(0.0, 0.0, 0.0, 1.0)),
+        ),
+    ));
+    scene.registry.spawn((
+        Transform::default(),
+        Text::new(
+            "I",
+            font,
+            TextStyle::new(48.0).with_color(Vec4::new(1.0, 0.0, 0.0, 1.0)),
+        )
+        .in_world(48.0)
+        .with_pivot(Vec2::new(0.5, 0.5)),
+    ));
+
+    let mut text_renderer = TextRenderer::new();
+    let mut batcher = SpriteBatcher::new();
+    text_renderer.queue_scene(&mut scene, &mut batcher);
+    let view_projection = Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, -1.0, 1.0);
+    let frame = RenderFrame {
+        clear_color: Vec4::new(0.5, 0.5, 0.5, 1.0),
+        view_projection,
+        sprites: batcher.build(view_projection),
+        ..RenderFrame::default()
+    };
+    let mut renderer = Renderer::offscreen(128, 64, &[BackendKind::Software]).unwrap();
+    renderer.sync_textures(&scene.textures).unwrap();
+    renderer.render(&frame).unwrap();
+    let image = renderer.capture_frame().unwrap();
+
+    // Screen-space caption: white stem with a black border either side.
+    assert_eq!(image.pixel(32, 32), [255, 255, 255, 255]);
+    let row: Vec<[u8; 4]> = (16..32).map(|x| image.pixel(x, 32)).collect();
+    assert!(row.contains(&[0, 0, 0, 255]));
+    assert_eq!(image.pixel(4, 32), [128, 128, 128, 255]);
+
+    // World-space text is centered on the origin, in the middle of the target.
+    assert_eq!(image.pixel(64, 32), [255, 0, 0, 255]);
+}
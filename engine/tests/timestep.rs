@@ -0,0 +1,26 @@
+use meme_engine::time::FixedTimestep;
+
+#[test]
+fn accumulates_partial_frames() {
+    let mut timestep = FixedTimestep::new(60, 5);
+    let half = timestep.step_seconds() * 0.5;
+    assert_eq!(timestep.advance(half), 0);
+    assert!((timestep.alpha() - 0.5).abs() < 1e-4);
+    assert_eq!(timestep.advance(half), 1);
+    assert!(timestep.alpha() < 1e-3);
+}
+
+#[test]
+fn runs_multiple_steps_for_long_frames() {
+    let mut timestep = FixedTimestep::new(100, 5);
+    assert_eq!(timestep.advance(0.035), 3);
+    assert!((timestep.alpha() - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn clamps_spiral_of_death() {
+    let mut timestep = FixedTimestep::new(60, 4);
+    assert_eq!(timestep.advance(10.0), 4);
+    assert!(timestep.alpha() < 1.0);
+    assert_eq!(timestep.advance(0.0), 0);
+}
@@ -1,13 +1,224 @@
-use meme_engine::{Engine, EngineConfig};
+mod meme;
+
+use glam::{Quat, Vec2, Vec3, Vec4};
+use meme_engine::renderer::{LoopMode, Mesh, MeshRenderer};
+use meme_engine::scene::{Entity, Transform};
+use meme_engine::text::{Text, TextStyle};
+use meme_engine::{
+    CaptureConfig, Engine, EngineConfig, EngineContext, EngineEvent, Game, HeadlessConfig,
+    RecordingConfig,
+};
+use std::str::FromStr;
+use tracing::info;
+
+const DEFAULT_HEADLESS_SECONDS: f32 = 10.0;
+
+const USAGE: &str = "usage: meme_game [--headless] [--frames N] [--seconds S] \
+[--capture-every N] [--capture-dir DIR] [--record PATH] [--record-every N] [--loops N] \
+[--caption TEXT] [--debug-draw]\n       meme_game meme --help";
+
+#[derive(Default)]
+struct DemoGame {
+    frames: u64,
+    elapsed_seconds: f32,
+    cube: Option<Entity>,
+    caption: Option<String>,
+    width: u32,
+}
+
+impl DemoGame {
+    fn spawn_cube(&mut self, ctx: &mut EngineContext<'_>) {
+        let mut mesh = Mesh::cuboid(Vec3::ONE);
+        let face_colors = [
+            Vec4::new(0.95, 0.3, 0.3, 1.0),
+            Vec4::new(0.3, 0.95, 0.4, 1.0),
+            Vec4::new(0.3, 0.5, 0.95, 1.0),
+            Vec4::new(0.95, 0.85, 0.3, 1.0),
+            Vec4::new(0.85, 0.35, 0.95, 1.0),
+            Vec4::new(0.3, 0.9, 0.9, 1.0),
+        ];
+        mesh.colors = face_colors.iter().flat_map(|&color| [color; 4]).collect();
+        let mesh = ctx.scene.meshes.insert(mesh);
+        self.cube = Some(
+            ctx.scene
+                .registry
+                .spawn((Transform::default(), MeshRenderer::new(mesh))),
+        );
+    }
+
+    fn spawn_caption(&mut self, ctx: &mut EngineContext<'_>) {
+        let Some(caption) = self.caption.clone() else {
+            return;
+        };
+        let font = match meme::bundled_font() {
+            Ok(font) => ctx.scene.fonts.insert(font),
+            Err(err) => {
+                eprintln!("caption font failed to load: {err}");
+                return;
+            }
+        };
+        let text = Text::new(caption, font, TextStyle::meme(64.0)).with_pivot(Vec2::new(0.5, 0.0));
+        let position = Vec3::new(self.width as f32 * 0.5, 24.0, 0.0);
+        ctx.scene
+            .registry
+            .spawn((Transform::from_position(position), text));
+    }
+}
+
+impl Game for DemoGame {
+    fn on_event(&mut self, event: &EngineEvent, ctx: &mut EngineContext<'_>) {
+        match event {
+            EngineEvent::Startup => {
+                self.spawn_cube(ctx);
+                self.spawn_caption(ctx);
+                info!("demo game started");
+            }
+            EngineEvent::Frame { delta_seconds } => {
+                self.frames += 1;
+                self.elapsed_seconds += delta_seconds;
+                let t = self.elapsed_seconds;
+                if let Some(transform) = self
+                    .cube
+                    .and_then(|cube| ctx.scene.registry.get_mut::<Transform>(cube))
+                {
+                    transform.rotation =
+                        Quat::from_rotation_y(t * 0.8) * Quat::from_rotation_x(t * 0.6);
+                }
+            }
+            EngineEvent::Shutdown => info!(
+                "demo game ran {} frames over {:.2}s",
+                self.frames, self.elapsed_seconds
+            ),
+            EngineEvent::FixedUpdate { .. } => {}
+        }
+    }
+}
+
+// Applies the demo's flags to `config` and `game`, returning --seconds if given.
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+    config: &mut EngineConfig,
+    game: &mut DemoGame,
+) -> Result<Option<f32>, String> {
+    let mut seconds = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{name} expects a value\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--headless" => {
+                config.headless.get_or_insert_with(HeadlessConfig::default);
+            }
+            "--frames" => {
+                let frames = number("--frames", value("--frames")?)?;
+                config
+                    .headless
+                    .get_or_insert_with(HeadlessConfig::default)
+                    .max_frames = Some(frames);
+            }
+            "--capture-every" => {
+                let every = number("--capture-every", value("--capture-every")?)?;
+                config
+                    .capture
+                    .get_or_insert_with(CaptureConfig::default)
+                    .every_n_frames = every;
+            }
+            "--capture-dir" => {
+                let dir = value("--capture-dir")?;
+                config
+                    .capture
+                    .get_or_insert_with(CaptureConfig::default)
+                    .output_dir = dir.into();
+            }
+            "--seconds" => {
+                let text = value("--seconds")?;
+                let parsed: f32 = number("--seconds", text.clone())?;
+                if !(parsed.is_finite() && parsed > 0.0) {
+                    return Err(format!("invalid --seconds: {text}\n{USAGE}"));
+                }
+                seconds = Some(parsed);
+                config.headless.get_or_insert_with(HeadlessConfig::default);
+            }
+            "--record" => {
+                let path = value("--record")?;
+                config
+                    .recording
+                    .get_or_insert_with(RecordingConfig::default)
+                    .output = path.into();
+            }
+            "--record-every" => {
+                let every = number("--record-every", value("--record-every")?)?;
+                config
+                    .recording
+                    .get_or_insert_with(RecordingConfig::default)
+                    .every_n_frames = every;
+            }
+            "--loops" => {
+                let loops = number("--loops", value("--loops")?)?;
+                config
+                    .recording
+                    .get_or_insert_with(RecordingConfig::default)
+                    .loop_mode = match loops {
+                    0 => LoopMode::Forever,
+                    plays => LoopMode::Times(plays),
+                };
+            }
+            "--caption" => game.caption = Some(value("--caption")?),
+            "--debug-draw" => config.debug_draw = true,
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            other => return Err(format!("unknown argument: {other}\n{USAGE}")),
+        }
+    }
+    Ok(seconds)
+}
+
+fn number<T: FromStr>(name: &str, value: String) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {name}: {value}\n{USAGE}"))
+}
 
 fn main() {
-    let config = EngineConfig {
+    let mut config = EngineConfig {
         title: "Meme Engine Demo".to_string(),
         width: 1280,
         height: 720,
         target_fps: 60,
+        ..EngineConfig::default()
+    };
+
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("meme") {
+        args.next();
+        if let Err(err) = meme::run(args) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut game = DemoGame::default();
+    let seconds = match parse_args(args, &mut config, &mut game) {
+        Ok(seconds) => seconds,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
     };
 
+    if let Some(headless) = config.headless.as_mut() {
+        let to_frames = |seconds: f32| (seconds * config.target_fps as f32).round() as u64;
+        if let Some(seconds) = seconds {
+            headless.max_frames = Some(to_frames(seconds));
+        }
+        // Nothing else stops a headless run, so it always gets a frame budget.
+        if headless.max_frames.is_none() {
+            headless.max_frames = Some(to_frames(DEFAULT_HEADLESS_SECONDS));
+        }
+    }
+    game.width = config.width;
+
     let engine = match Engine::new(config) {
         Ok(engine) => engine,
         Err(err) => {
@@ -16,7 +227,7 @@ fn main() {
         }
     };
 
-    if let Err(err) = engine.run() {
+    if let Err(err) = engine.run_with(game) {
         eprintln!("engine runtime error: {err}");
     }
 }
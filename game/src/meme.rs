@@ -0,0 +1,109 @@
+use glam::Vec2;
+use meme_engine::meme::{Caption, CaptionPosition, MemeCompositor, MemeConfig};
+use meme_engine::renderer::Image;
+use meme_engine::text::{Font, TextStyle};
+use std::path::PathBuf;
+
+const BUNDLED_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+pub const USAGE: &str = "usage: meme_game meme <image> [--top TEXT] [--bottom TEXT] \
+[--center TEXT] [--at X,Y TEXT] [--size PX] [--font PATH] [--out PATH]";
+
+pub fn bundled_font() -> Result<Font, meme_engine::EngineError> {
+    Font::from_bytes(BUNDLED_FONT.to_vec())
+}
+
+#[derive(Debug, Default)]
+struct MemeArgs {
+    image: Option<PathBuf>,
+    out: Option<PathBuf>,
+    font: Option<PathBuf>,
+    size: Option<f32>,
+    captions: Vec<Caption>,
+}
+
+// Renders a captioned image straight to a PNG through the offscreen renderer;
+// no window or game loop is created.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = parse(args)?;
+    let image_path = args.image.ok_or_else(|| USAGE.to_string())?;
+    let base = Image::load(&image_path).map_err(|err| err.to_string())?;
+    let font = match &args.font {
+        Some(path) => Font::load(path),
+        None => bundled_font(),
+    }
+    .map_err(|err| err.to_string())?;
+
+    let compositor = MemeCompositor::new(font, MemeConfig::default());
+    let mut captions = args.captions;
+    if let Some(size) = args.size {
+        let default = compositor.default_style(base.width(), base.height());
+        let mut style = TextStyle::meme(size);
+        style.max_width = default.max_width;
+        for caption in &mut captions {
+            caption.style = Some(style);
+        }
+    }
+    let out = args.out.unwrap_or_else(|| PathBuf::from("meme.png"));
+    compositor
+        .compose(&base, &captions)
+        .and_then(|image| image.save_png(&out))
+        .map_err(|err| err.to_string())?;
+    println!("wrote {}", out.display());
+    Ok(())
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<MemeArgs, String> {
+    let mut parsed = MemeArgs::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{name} expects a value\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--top" => parsed.captions.push(Caption::top(value("--top")?)),
+            "--bottom" => parsed.captions.push(Caption::bottom(value("--bottom")?)),
+            "--center" => parsed
+                .captions
+                .push(Caption::new(value("--center")?, CaptionPosition::Center)),
+            "--at" => {
+                let position = parse_position(&value("--at")?)?;
+                let text = value("--at")?;
+                parsed
+                    .captions
+                    .push(Caption::new(text, CaptionPosition::At(position)));
+            }
+            "--size" => {
+                let size = value("--size")?;
+                parsed.size = Some(
+                    size.parse()
+                        .ok()
+                        .filter(|&size: &f32| size > 0.0)
+                        .ok_or_else(|| format!("invalid --size: {size}"))?,
+                );
+            }
+            "--font" => parsed.font = Some(value("--font")?.into()),
+            "--out" | "-o" => parsed.out = Some(value("--out")?.into()),
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            other if other.starts_with('-') => {
+                return Err(format!("unknown argument: {other}\n{USAGE}"))
+            }
+            other if parsed.image.is_none() => parsed.image = Some(other.into()),
+            other => return Err(format!("unexpected argument: {other}\n{USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+// Normalized "x,y" with 0,0 at the top-left of the image.
+fn parse_position(value: &str) -> Result<Vec2, String> {
+    let invalid = || format!("invalid --at position (expected X,Y in 0..1): {value}");
+    let (x, y) = value.split_once(',').ok_or_else(invalid)?;
+    let x: f32 = x.trim().parse().map_err(|_| invalid())?;
+    let y: f32 = y.trim().parse().map_err(|_| invalid())?;
+    // Also rejects NaN and infinities, which `parse` accepts.
+    if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+        return Err(invalid());
+    }
+    Ok(Vec2::new(x, y))
+}